@@ -30,15 +30,42 @@ pub struct CompileCommand {
 
 impl CompileCommand {
     /// Get the compilation arguments as a vector.
+    /// Response files (`@file`) are expanded in place.
     pub fn get_args(&self) -> Vec<String> {
-        if let Some(args) = &self.arguments {
+        let args = if let Some(args) = &self.arguments {
             args.clone()
         } else if let Some(cmd) = &self.command {
-            // Simple space-split (doesn't handle quoted strings properly)
-            cmd.split_whitespace().map(|s| s.to_string()).collect()
+            split_command_line(cmd)
         } else {
             Vec::new()
+        };
+        self.expand_response_files(args, 0)
+    }
+
+    /// Replace each `@file` argument with the arguments read from that file.
+    /// Relative paths are resolved against the command's working directory.
+    /// Unreadable files are kept as a literal argument, like GCC and Clang do.
+    fn expand_response_files(&self, args: Vec<String>, depth: usize) -> Vec<String> {
+        let mut expanded = Vec::with_capacity(args.len());
+        for arg in args {
+            let Some(file) = arg.strip_prefix('@').filter(|f| !f.is_empty()) else {
+                expanded.push(arg);
+                continue;
+            };
+            // Guard against response files that (indirectly) include themselves
+            if depth >= MAX_RESPONSE_FILE_DEPTH {
+                expanded.push(arg);
+                continue;
+            }
+            match std::fs::read_to_string(self.directory.join(file)) {
+                Ok(content) => {
+                    let nested = split_command_line(&content);
+                    expanded.extend(self.expand_response_files(nested, depth + 1));
+                }
+                Err(_) => expanded.push(arg),
+            }
         }
+        expanded
     }
 
     /// Extract include directories from the arguments.
@@ -152,6 +179,71 @@ impl CompileCommand {
     }
 }
 
+/// Maximum nesting of response files referencing other response files.
+const MAX_RESPONSE_FILE_DEPTH: usize = 16;
+
+/// Split a command line (or response file contents) into arguments.
+/// Whitespace separates arguments; single quotes, double quotes, and
+/// backslash escapes group characters into one argument, as in a POSIX shell.
+fn split_command_line(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_arg = true;
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    current.push(c);
+                }
+            }
+            '"' => {
+                in_arg = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some(next @ ('"' | '\\' | '$' | '`')) => current.push(next),
+                            Some(next) => {
+                                current.push('\\');
+                                current.push(next);
+                            }
+                            None => current.push('\\'),
+                        },
+                        _ => current.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_arg = true;
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            _ => {
+                in_arg = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+
+    args
+}
+
 /// Collection of compile commands (from compile_commands.json).
 #[derive(Debug, Clone)]
 pub struct CompileCommands {
@@ -248,6 +340,66 @@ mod tests {
         assert_eq!(defines, vec!["DEBUG=1"]);
     }
 
+    #[test]
+    fn test_split_command_line_quoting() {
+        let args = split_command_line(
+            r#"g++ -DNAME="hello world" '-I/path with spaces' -DQ=\"x\" a\ b.cc"#,
+        );
+        assert_eq!(
+            args,
+            vec![
+                "g++",
+                "-DNAME=hello world",
+                "-I/path with spaces",
+                "-DQ=\"x\"",
+                "a b.cc",
+            ]
+        );
+        assert!(split_command_line("  \n\t ").is_empty());
+        assert_eq!(split_command_line("''"), vec![""]);
+    }
+
+    #[test]
+    fn test_response_file_expansion() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("flags.rsp"),
+            "-I../include \"-I/opt/my libs\"\n-DUSE_RSP=1 @nested.rsp\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("nested.rsp"), "-std=c++20").unwrap();
+
+        let json = format!(
+            r#"[
+                {{
+                    "directory": "{}",
+                    "file": "main.cc",
+                    "command": "g++ @flags.rsp -DDIRECT -c main.cc"
+                }},
+                {{
+                    "directory": "{}",
+                    "file": "other.cc",
+                    "arguments": ["g++", "@missing.rsp", "-c", "other.cc"]
+                }}
+            ]"#,
+            dir.path().display(),
+            dir.path().display()
+        );
+        let cmds = CompileCommands::from_str(&json).unwrap();
+
+        let cmd = &cmds.commands()[0];
+        assert_eq!(
+            cmd.get_includes(),
+            vec![PathBuf::from("../include"), PathBuf::from("/opt/my libs")]
+        );
+        assert_eq!(cmd.get_defines(), vec!["USE_RSP=1", "DIRECT"]);
+        assert_eq!(cmd.get_std(), Some("c++20".to_string()));
+
+        // An unreadable response file is passed through unchanged
+        let other = &cmds.commands()[1];
+        assert!(other.get_args().contains(&"@missing.rsp".to_string()));
+    }
+
     #[test]
     fn test_find_command() {
         let json = r#"[
@@ -290,6 +290,31 @@ impl TargetConfig {
         self.includes = includes.iter().map(|s| s.to_string()).collect();
         self
     }
+
+    /// File name of the artifact a build of this target writes to the output directory:
+    /// the executable (`<target>`) or library (`lib<name>.a` / `lib<name>.so`), following
+    /// the usual linker conventions. Nothing in this workspace links targets yet, and
+    /// object files have no name derived from the target, so `fragile clean` leaves them.
+    /// Fails for target names that aren't plain file names, which could point outside it.
+    pub fn artifact_name(&self) -> crate::Result<String> {
+        if self.name.is_empty()
+            || self.name == "."
+            || self.name == ".."
+            || self.name.contains(['/', '\\'])
+        {
+            return Err(crate::BuildError::Validation(format!(
+                "target name `{}` is not a plain file name",
+                self.name
+            )));
+        }
+        // Library targets are often already named libfoo; don't produce liblibfoo.a
+        let lib_name = self.name.strip_prefix("lib").unwrap_or(&self.name);
+        Ok(match self.target_type {
+            TargetType::Executable => self.name.clone(),
+            TargetType::StaticLibrary => format!("lib{}.a", lib_name),
+            TargetType::SharedLibrary => format!("lib{}.so", lib_name),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -429,6 +454,30 @@ lib_paths = ["/opt/lib"]
         assert!(lib_paths.contains(&"/usr/local/lib".to_string()));
     }
 
+    #[test]
+    fn test_artifact_name() {
+        let exe = TargetConfig::executable("server").with_sources(&["src/main.cc"]);
+        assert_eq!(exe.artifact_name().unwrap(), "server");
+
+        let lib = TargetConfig::static_library("libmako").with_sources(&["src/mako/txn.cc"]);
+        assert_eq!(lib.artifact_name().unwrap(), "libmako.a");
+
+        let shared = TargetConfig {
+            target_type: TargetType::SharedLibrary,
+            ..TargetConfig::executable("util")
+        };
+        assert_eq!(shared.artifact_name().unwrap(), "libutil.so");
+
+        // Names that would resolve outside the output directory are rejected
+        for name in ["../server", "bin/server", "..", ""] {
+            assert!(
+                TargetConfig::executable(name).artifact_name().is_err(),
+                "{:?} accepted",
+                name
+            );
+        }
+    }
+
     #[test]
     fn test_circular_deps_detection() {
         // No circular deps
@@ -45,6 +45,7 @@ fn count_nodes(node: &ClangNode, depth: usize, max_depth: usize) {
             is_class,
             is_definition,
             fields,
+            ..
         } => {
             let kind = if *is_class { "class" } else { "struct" };
             let def_marker = if *is_definition {
@@ -169,6 +169,8 @@ pub enum ClangNodeKind {
         /// Whether this is a parameter pack (typename... Args)
         is_pack: bool,
     },
+    /// Non-type template parameter (e.g., int N in template<int N>)
+    NonTypeTemplateParmDecl { name: String, ty: CppType },
     /// Parameter declaration
     ParmVarDecl { name: String, ty: CppType },
     /// Variable declaration
@@ -176,6 +178,20 @@ pub enum ClangNodeKind {
         name: String,
         ty: CppType,
         has_init: bool,
+        /// Whether the variable has thread storage duration (`thread_local`)
+        is_thread_local: bool,
+    },
+    /// Structured binding declaration (e.g., `auto [a, b] = expr;`).
+    /// Children are the BindingDecls (as Unknown) followed by the initializer.
+    DecompositionDecl {
+        /// Names of the bindings, in declaration order
+        names: Vec<String>,
+        /// Type of the decomposed object (reference stripped)
+        ty: CppType,
+        /// Whether the bindings refer into the source (`auto&` / `auto&&`)
+        is_ref: bool,
+        /// Whether the decomposed object is const (`const auto&`, or a const source)
+        is_const: bool,
     },
     /// Struct/class declaration
     RecordDecl {
@@ -183,6 +199,8 @@ pub enum ClangNodeKind {
         is_class: bool,
         is_definition: bool,
         fields: Vec<(String, CppType)>,
+        /// Alignment in bytes when raised with `alignas`
+        alignment: Option<u64>,
     },
     /// Union declaration
     UnionDecl {
@@ -239,6 +257,9 @@ pub enum ClangNodeKind {
         class_name: String,
         is_definition: bool,
         access: AccessSpecifier,
+        /// Whether the destructor is declared `noexcept(false)`; destructors are
+        /// otherwise implicitly noexcept
+        may_throw: bool,
     },
     /// Member reference (e.g., in member initializer lists)
     MemberRef { name: String },
@@ -316,7 +337,10 @@ pub enum ClangNodeKind {
     /// Return statement
     ReturnStmt,
     /// If statement
-    IfStmt,
+    IfStmt {
+        /// Whether this is `if constexpr`, whose condition is decided per instantiation
+        is_constexpr: bool,
+    },
     /// While statement
     WhileStmt,
     /// For statement
@@ -347,6 +371,16 @@ pub enum ClangNodeKind {
     },
     /// Default statement in switch
     DefaultStmt,
+    /// Labeled statement (`name: stmt`); the labeled statement is the only child
+    LabelStmt {
+        /// The label name
+        name: String,
+    },
+    /// Goto statement (`goto label;`)
+    GotoStmt {
+        /// The target label name
+        label: String,
+    },
 
     // Expressions
     /// Integer literal with value and type
@@ -370,6 +404,9 @@ pub enum ClangNodeKind {
     NullPtrLiteral,
     /// String literal
     StringLiteral(String),
+    /// Wide or unicode string literal (`L"..."`, `u"..."`, `U"..."`, `u8"..."` in C++20),
+    /// with the decoded text and the character type it is encoded in.
+    WideStringLiteral { value: String, char_ty: CppType },
     /// Evaluated expression (for default arguments and compile-time constants)
     EvaluatedExpr {
         /// The evaluated integer value (if available)
@@ -438,6 +475,17 @@ pub enum ClangNodeKind {
         type_args: Vec<CppType>,
     },
 
+    /// `sizeof`/`alignof` expression (Clang's UnaryExprOrTypeTraitExpr).
+    /// For `sizeof(expr)` the operand is the single expression child.
+    UnaryExprOrTypeTraitExpr {
+        /// Whether this is `sizeof` or `alignof`
+        trait_kind: UnaryTypeTraitKind,
+        /// The type operand of `sizeof(T)`/`alignof(T)` (None for an expression operand)
+        arg_type: Option<CppType>,
+        /// Result type (size_t)
+        ty: CppType,
+    },
+
     // C++20 Concepts
     /// Concept definition (e.g., template<typename T> concept Integral = ...)
     ConceptDecl {
@@ -517,6 +565,9 @@ pub enum ClangNodeKind {
         capture_default: CaptureDefault,
         /// Explicit captures: (name, by_ref)
         captures: Vec<(String, bool)>,
+        /// Init-captures (`[x = expr]`): name and position among the explicit captures,
+        /// which is also the position of its initializer among the capture-initializer children
+        init_captures: Vec<(String, usize)>,
     },
 
     // C++ RTTI (Run-Time Type Information)
@@ -556,6 +607,15 @@ pub enum ClangNodeKind {
     Unknown(String),
 }
 
+/// Operator of a UnaryExprOrTypeTraitExpr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryTypeTraitKind {
+    /// sizeof(T) or sizeof expr
+    SizeOf,
+    /// alignof(T)
+    AlignOf,
+}
+
 /// Kinds of built-in type traits (Clang intrinsics).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TypeTraitKind {
@@ -587,6 +647,27 @@ pub enum TypeTraitKind {
     Unknown,
 }
 
+impl TypeTraitKind {
+    /// Map a standard variable template (e.g. `is_same_v`) to its type trait.
+    pub fn from_variable_template(name: &str) -> Option<Self> {
+        match name {
+            "is_integral_v" => Some(Self::IsIntegral),
+            "is_signed_v" => Some(Self::IsSigned),
+            "is_unsigned_v" => Some(Self::IsUnsigned),
+            "is_floating_point_v" => Some(Self::IsFloatingPoint),
+            "is_arithmetic_v" => Some(Self::IsArithmetic),
+            "is_scalar_v" => Some(Self::IsScalar),
+            "is_pointer_v" => Some(Self::IsPointer),
+            "is_reference_v" => Some(Self::IsReference),
+            "is_same_v" => Some(Self::IsSame),
+            "is_base_of_v" => Some(Self::IsBaseOf),
+            "is_trivially_copyable_v" => Some(Self::IsTriviallyCopyable),
+            "is_trivially_destructible_v" => Some(Self::IsTriviallyDestructible),
+            _ => None,
+        }
+    }
+}
+
 /// A single requirement inside a requires expression.
 #[derive(Debug, Clone)]
 pub enum Requirement {
@@ -658,6 +739,11 @@ pub enum BinaryOp {
     ShrAssign,
     // Comma
     Comma,
+    // Pointer-to-member access
+    /// `obj.*pm`
+    PtrMemD,
+    /// `ptr->*pm`
+    PtrMemI,
 }
 
 /// Unary operators.
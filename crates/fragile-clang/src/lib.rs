@@ -18,10 +18,10 @@ mod types;
 
 pub use ast::{
     AccessSpecifier, BinaryOp, ClangAst, ClangNode, ClangNodeKind, ConstructorKind, Requirement,
-    TypeTraitKind, UnaryOp,
+    TypeTraitKind, UnaryOp, UnaryTypeTraitKind,
 };
 pub use ast_codegen::AstCodeGen;
-pub use parse::ClangParser;
+pub use parse::{ClangErrors, ClangParser};
 pub use types::{CppType, TypeProperties, TypeTraitEvaluator, TypeTraitResult};
 
 use miette::Result;
@@ -2,14 +2,39 @@
 
 use crate::ast::{
     AccessSpecifier, BinaryOp, CaptureDefault, CastKind, ClangAst, ClangNode, ClangNodeKind,
-    ConstructorKind, CoroutineInfo, CoroutineKind, Requirement, SourceLocation, UnaryOp,
+    ConstructorKind, CoroutineInfo, CoroutineKind, Requirement, SourceLocation, TypeTraitKind,
+    UnaryOp, UnaryTypeTraitKind,
 };
 use crate::types::CppType;
+use fragile_common::Diagnostic;
 use miette::{miette, Result};
 use std::ffi::{CStr, CString};
 use std::path::Path;
 use std::ptr;
 
+/// Clang errors reported in user code (errors in system headers are ignored).
+///
+/// `parse_file` returns this inside its `miette::Report`, so callers that want the
+/// individual diagnostics (e.g. for JSON output) can `downcast_ref` it.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+#[error("Clang errors in user code:\n{}", format_clang_errors(.diagnostics))]
+pub struct ClangErrors {
+    /// One error diagnostic per Clang error, with its file/line/column location
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Format errors as `file:line:column: message` lines.
+fn format_clang_errors(diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(|diag| match &diag.location {
+            Some(loc) => format!("{}:{}:{}: {}", loc.file, loc.line, loc.column, diag.message),
+            None => diag.message.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Parser that uses libclang to parse C++ source files.
 pub struct ClangParser {
     index: clang_sys::CXIndex,
@@ -432,7 +457,10 @@ impl ClangParser {
                         .any(|pattern| msg.contains(pattern));
 
                     if !is_system_header && !is_ignored {
-                        user_errors.push(format!("{}:{}:{}: {}", file_name, line, column, msg));
+                        let mut error =
+                            Diagnostic::error(msg).with_location(file_name, line, column);
+                        error.span = Some((offset as usize, 0).into());
+                        user_errors.push(error);
                     }
                 }
                 clang_sys::clang_disposeDiagnostic(diag);
@@ -441,10 +469,10 @@ impl ClangParser {
             // Only fail if there are errors in user code
             if !user_errors.is_empty() {
                 clang_sys::clang_disposeTranslationUnit(tu);
-                return Err(miette!(
-                    "Clang errors in user code:\n{}",
-                    user_errors.join("\n")
-                ));
+                return Err(ClangErrors {
+                    diagnostics: user_errors,
+                }
+                .into());
             }
 
             // Get the cursor for the translation unit
@@ -616,6 +644,274 @@ impl ClangParser {
         }
     }
 
+    /// Check if a function is declared `noexcept(false)`.
+    fn is_noexcept_false(&self, cursor: clang_sys::CXCursor) -> bool {
+        unsafe {
+            let tu = clang_sys::clang_Cursor_getTranslationUnit(cursor);
+            let extent = clang_sys::clang_getCursorExtent(cursor);
+            let mut tokens: *mut clang_sys::CXToken = ptr::null_mut();
+            let mut num_tokens: u32 = 0;
+
+            clang_sys::clang_tokenize(tu, extent, &mut tokens, &mut num_tokens);
+
+            let mut token_strs = Vec::with_capacity(num_tokens as usize);
+            for i in 0..num_tokens {
+                let token = *tokens.add(i as usize);
+                let spelling = clang_sys::clang_getTokenSpelling(tu, token);
+                token_strs.push(cx_string_to_string(spelling));
+            }
+
+            if !tokens.is_null() {
+                clang_sys::clang_disposeTokens(tu, tokens, num_tokens);
+            }
+
+            token_strs
+                .windows(4)
+                .any(|w| w[0] == "noexcept" && w[1] == "(" && w[2] == "false" && w[3] == ")")
+        }
+    }
+
+    /// Check if an if statement is `if constexpr` (the token after `if`).
+    /// Only the tokens before the first child (the init statement or condition) are read,
+    /// so large bodies are not re-tokenized.
+    fn is_constexpr_if(&self, cursor: clang_sys::CXCursor) -> bool {
+        unsafe {
+            let tu = clang_sys::clang_Cursor_getTranslationUnit(cursor);
+            let extent = clang_sys::clang_getCursorExtent(cursor);
+
+            extern "C" fn find_first_child(
+                c: clang_sys::CXCursor,
+                _parent: clang_sys::CXCursor,
+                data: clang_sys::CXClientData,
+            ) -> clang_sys::CXChildVisitResult {
+                unsafe { *(data as *mut clang_sys::CXCursor) = c };
+                clang_sys::CXChildVisit_Break
+            }
+            let mut first_child = clang_sys::clang_getNullCursor();
+            clang_sys::clang_visitChildren(
+                cursor,
+                find_first_child,
+                &mut first_child as *mut clang_sys::CXCursor as clang_sys::CXClientData,
+            );
+            let head = if clang_sys::clang_Cursor_isNull(first_child) != 0 {
+                extent
+            } else {
+                clang_sys::clang_getRange(
+                    clang_sys::clang_getRangeStart(extent),
+                    clang_sys::clang_getRangeStart(clang_sys::clang_getCursorExtent(first_child)),
+                )
+            };
+
+            let mut tokens: *mut clang_sys::CXToken = ptr::null_mut();
+            let mut num_tokens: u32 = 0;
+
+            clang_sys::clang_tokenize(tu, head, &mut tokens, &mut num_tokens);
+
+            let is_constexpr = num_tokens >= 2 && {
+                let spelling = clang_sys::clang_getTokenSpelling(tu, *tokens.add(1));
+                cx_string_to_string(spelling) == "constexpr"
+            };
+
+            if !tokens.is_null() {
+                clang_sys::clang_disposeTokens(tu, tokens, num_tokens);
+            }
+
+            is_constexpr
+        }
+    }
+
+    /// Parse a `sizeof`/`alignof` expression using its tokens: the keyword selects
+    /// the operator, and a parenthesized operand is recorded as a type spelling.
+    fn try_parse_unary_type_trait(&self, cursor: clang_sys::CXCursor) -> Option<ClangNodeKind> {
+        unsafe {
+            let tu = clang_sys::clang_Cursor_getTranslationUnit(cursor);
+            let extent = clang_sys::clang_getCursorExtent(cursor);
+            let mut tokens: *mut clang_sys::CXToken = ptr::null_mut();
+            let mut num_tokens: u32 = 0;
+
+            clang_sys::clang_tokenize(tu, extent, &mut tokens, &mut num_tokens);
+
+            let token_strs: Vec<String> = (0..num_tokens)
+                .map(|i| {
+                    cx_string_to_string(clang_sys::clang_getTokenSpelling(
+                        tu,
+                        *tokens.add(i as usize),
+                    ))
+                })
+                .collect();
+
+            if !tokens.is_null() {
+                clang_sys::clang_disposeTokens(tu, tokens, num_tokens);
+            }
+
+            let trait_kind = match token_strs.first()?.as_str() {
+                "sizeof" => UnaryTypeTraitKind::SizeOf,
+                "alignof" | "_Alignof" | "__alignof" | "__alignof__" => UnaryTypeTraitKind::AlignOf,
+                // sizeof...(Args), vec_step, etc. are not handled
+                _ => return None,
+            };
+
+            // sizeof(T): the tokens between the outer parentheses spell the type.
+            // For sizeof(expr) this is meaningless; codegen uses the expression child instead.
+            let arg_type = if token_strs.get(1).map(String::as_str) == Some("(")
+                && token_strs.last().map(String::as_str) == Some(")")
+            {
+                let inner = &token_strs[2..token_strs.len() - 1];
+                Some(self.parse_type_from_string(&inner.join(" ")))
+            } else {
+                None
+            };
+
+            let ty = self.convert_type(clang_sys::clang_getCursorType(cursor));
+            Some(ClangNodeKind::UnaryExprOrTypeTraitExpr {
+                trait_kind,
+                arg_type,
+                ty,
+            })
+        }
+    }
+
+    /// Try to parse a structured binding declaration (`auto [a, b] = expr;`).
+    /// libclang exposes DecompositionDecl only as CXCursor_UnexposedDecl, so the
+    /// binding names and reference qualifier are recovered from the tokens.
+    fn try_parse_decomposition_decl(&self, cursor: clang_sys::CXCursor) -> Option<ClangNodeKind> {
+        unsafe {
+            let tu = clang_sys::clang_Cursor_getTranslationUnit(cursor);
+            let extent = clang_sys::clang_getCursorExtent(cursor);
+            let mut tokens: *mut clang_sys::CXToken = ptr::null_mut();
+            let mut num_tokens: u32 = 0;
+
+            clang_sys::clang_tokenize(tu, extent, &mut tokens, &mut num_tokens);
+
+            let token_strs: Vec<String> = (0..num_tokens)
+                .map(|i| {
+                    cx_string_to_string(clang_sys::clang_getTokenSpelling(
+                        tu,
+                        *tokens.add(i as usize),
+                    ))
+                })
+                .collect();
+
+            if !tokens.is_null() {
+                clang_sys::clang_disposeTokens(tu, tokens, num_tokens);
+            }
+
+            // The declaration starts with `auto`, optionally cv/static-qualified,
+            // and the binding list follows the (optional) `&`/`&&`.
+            let open = token_strs.iter().position(|t| t == "[")?;
+            let prefix = &token_strs[..open];
+            if !prefix.iter().any(|t| t == "auto") {
+                return None;
+            }
+            let is_ref = prefix.iter().any(|t| t == "&" || t == "&&");
+            let close = open + token_strs[open..].iter().position(|t| t == "]")?;
+            let names: Vec<String> = token_strs[open + 1..close]
+                .iter()
+                .filter(|t| t.as_str() != ",")
+                .cloned()
+                .collect();
+            if names.is_empty() {
+                return None;
+            }
+
+            let (ty, is_const) = match self.convert_type(clang_sys::clang_getCursorType(cursor)) {
+                CppType::Reference {
+                    referent, is_const, ..
+                } => (*referent, is_const),
+                other => (other, false),
+            };
+            // `auto& [a, b] = c;` deduces a const reference when `c` is const
+            let is_const = is_const || prefix.iter().any(|t| t == "const");
+            Some(ClangNodeKind::DecompositionDecl {
+                names,
+                ty,
+                is_ref,
+                is_const,
+            })
+        }
+    }
+
+    /// Get the spellings of the template arguments of a template-id expression
+    /// (e.g. `["T", "std::string"]` for `std::is_same_v<T, std::string>`).
+    /// libclang exposes no template arguments on a DeclRefExpr, so they are read from the tokens.
+    fn get_template_arg_spellings(&self, cursor: clang_sys::CXCursor) -> Vec<String> {
+        unsafe {
+            let tu = clang_sys::clang_Cursor_getTranslationUnit(cursor);
+            let extent = clang_sys::clang_getCursorExtent(cursor);
+            let mut tokens: *mut clang_sys::CXToken = ptr::null_mut();
+            let mut num_tokens: u32 = 0;
+
+            clang_sys::clang_tokenize(tu, extent, &mut tokens, &mut num_tokens);
+
+            let mut token_strs: Vec<String> = Vec::new();
+            for i in 0..num_tokens {
+                let spelling = clang_sys::clang_getTokenSpelling(tu, *tokens.add(i as usize));
+                match cx_string_to_string(spelling).as_str() {
+                    // `>>` closing two template argument lists is two `>` tokens
+                    ">>" => token_strs.extend([">".to_string(), ">".to_string()]),
+                    token => token_strs.push(token.to_string()),
+                }
+            }
+
+            if !tokens.is_null() {
+                clang_sys::clang_disposeTokens(tu, tokens, num_tokens);
+            }
+
+            // Split the tokens between the outermost < and > on top-level commas
+            let mut args = Vec::new();
+            let mut current: Vec<String> = Vec::new();
+            let mut depth = 0;
+            for token in token_strs {
+                match token.as_str() {
+                    "<" => {
+                        depth += 1;
+                        if depth == 1 {
+                            continue;
+                        }
+                    }
+                    ">" => {
+                        depth -= 1;
+                        if depth == 0 {
+                            args.push(join_type_tokens(&current));
+                            break;
+                        }
+                    }
+                    "," if depth == 1 => {
+                        args.push(join_type_tokens(&std::mem::take(&mut current)));
+                        continue;
+                    }
+                    _ => {}
+                }
+                if depth >= 1 {
+                    current.push(token);
+                }
+            }
+
+            args
+        }
+    }
+
+    /// Check whether the declaration a cursor refers to is in namespace `std`
+    /// (directly or through an inline namespace such as libc++'s `std::__1`).
+    fn refers_to_std(&self, cursor: clang_sys::CXCursor) -> bool {
+        unsafe {
+            let mut current = clang_sys::clang_getCursorSemanticParent(
+                clang_sys::clang_getCursorReferenced(cursor),
+            );
+            while clang_sys::clang_Cursor_isNull(current) == 0
+                && clang_sys::clang_getCursorKind(current) != clang_sys::CXCursor_TranslationUnit
+            {
+                if clang_sys::clang_getCursorKind(current) == clang_sys::CXCursor_Namespace
+                    && cursor_spelling(current) == "std"
+                {
+                    return true;
+                }
+                current = clang_sys::clang_getCursorSemanticParent(current);
+            }
+            false
+        }
+    }
+
     /// Check if a member reference expression uses arrow (->) or dot (.) access.
     /// We need to find the operator immediately before the member name, not any arrow
     /// anywhere in the expression (e.g., `c->data[idx].val` should return false for the .val part).
@@ -975,7 +1271,23 @@ impl ClangParser {
                     } else {
                         // Regular variable declaration
                         let has_init = false; // Will be determined by children
-                        ClangNodeKind::VarDecl { name, ty, has_init }
+                        let is_thread_local =
+                            clang_sys::clang_getCursorTLSKind(cursor) != clang_sys::CXTLS_None;
+                        ClangNodeKind::VarDecl {
+                            name,
+                            ty,
+                            has_init,
+                            is_thread_local,
+                        }
+                    }
+                }
+
+                clang_sys::CXCursor_UnexposedDecl => {
+                    if let Some(decomposition) = self.try_parse_decomposition_decl(cursor) {
+                        decomposition
+                    } else {
+                        let kind_spelling = clang_sys::clang_getCursorKindSpelling(kind);
+                        ClangNodeKind::Unknown(cx_string_to_string(kind_spelling))
                     }
                 }
 
@@ -1020,12 +1332,42 @@ impl ClangParser {
                     } else {
                         name
                     };
+                    // alignas shows up as an AlignedAttr child; the record type's
+                    // alignment then reflects the requested value
+                    extern "C" fn find_aligned_attr(
+                        child: clang_sys::CXCursor,
+                        _parent: clang_sys::CXCursor,
+                        data: clang_sys::CXClientData,
+                    ) -> clang_sys::CXChildVisitResult {
+                        unsafe {
+                            if clang_sys::clang_getCursorKind(child)
+                                == clang_sys::CXCursor_AlignedAttr
+                            {
+                                *(data as *mut bool) = true;
+                                return clang_sys::CXChildVisit_Break;
+                            }
+                        }
+                        clang_sys::CXChildVisit_Continue
+                    }
+
+                    let mut has_aligned_attr = false;
+                    if is_definition && clang_sys::clang_Cursor_hasAttrs(cursor) != 0 {
+                        clang_sys::clang_visitChildren(
+                            cursor,
+                            find_aligned_attr,
+                            &mut has_aligned_attr as *mut bool as clang_sys::CXClientData,
+                        );
+                    }
+                    let align = clang_sys::clang_Type_getAlignOf(cursor_type);
+                    let alignment = (has_aligned_attr && align > 0).then_some(align as u64);
+
                     // Fields will be collected from children
                     ClangNodeKind::RecordDecl {
                         name: final_name,
                         is_class,
                         is_definition,
                         fields: Vec::new(),
+                        alignment,
                     }
                 }
 
@@ -1175,10 +1517,12 @@ impl ClangParser {
                     let class_name = self.get_parent_class_name(cursor);
                     let is_definition = clang_sys::clang_isCursorDefinition(cursor) != 0;
                     let access = self.get_access_specifier(cursor);
+                    let may_throw = self.is_noexcept_false(cursor);
                     ClangNodeKind::DestructorDecl {
                         class_name,
                         is_definition,
                         access,
+                        may_throw,
                     }
                 }
 
@@ -1288,7 +1632,9 @@ impl ClangParser {
                 // Statements
                 clang_sys::CXCursor_CompoundStmt => ClangNodeKind::CompoundStmt,
                 clang_sys::CXCursor_ReturnStmt => ClangNodeKind::ReturnStmt,
-                clang_sys::CXCursor_IfStmt => ClangNodeKind::IfStmt,
+                clang_sys::CXCursor_IfStmt => ClangNodeKind::IfStmt {
+                    is_constexpr: self.is_constexpr_if(cursor),
+                },
                 clang_sys::CXCursor_WhileStmt => ClangNodeKind::WhileStmt,
                 clang_sys::CXCursor_ForStmt => ClangNodeKind::ForStmt,
                 // CXCursor_CXXForRangeStmt = 225
@@ -1349,6 +1695,16 @@ impl ClangParser {
                     ClangNodeKind::CaseStmt { value: case_value }
                 }
                 clang_sys::CXCursor_DefaultStmt => ClangNodeKind::DefaultStmt,
+                clang_sys::CXCursor_LabelStmt => ClangNodeKind::LabelStmt {
+                    name: cursor_spelling(cursor),
+                },
+                clang_sys::CXCursor_GotoStmt => {
+                    // The referenced cursor of a goto is the target LabelStmt
+                    let target = clang_sys::clang_getCursorReferenced(cursor);
+                    ClangNodeKind::GotoStmt {
+                        label: cursor_spelling(target),
+                    }
+                }
 
                 // C++ Exception Handling
                 clang_sys::CXCursor_CXXTryStmt => ClangNodeKind::TryStmt,
@@ -1464,8 +1820,14 @@ impl ClangParser {
                         result
                     };
 
-                    // In C++, character literals have type 'char' (i8 in Rust)
-                    let cpp_type = Some(CppType::Char { signed: true });
+                    // In C++, character literals have type 'char' (i8 in Rust); prefixed
+                    // literals like L'x' and u'x' keep their wide character type
+                    let char_ty = self.convert_type(clang_sys::clang_getCursorType(cursor));
+                    let cpp_type = Some(if char_ty.is_wide_char() {
+                        char_ty
+                    } else {
+                        CppType::Char { signed: true }
+                    });
 
                     ClangNodeKind::IntegerLiteral { value, cpp_type }
                 }
@@ -1530,6 +1892,20 @@ impl ClangParser {
                 }
 
                 clang_sys::CXCursor_StringLiteral => {
+                    // Wide literals can't be evaluated to a C string, so decode their spelling
+                    if let CppType::Array { element, .. } =
+                        self.convert_type(clang_sys::clang_getCursorType(cursor))
+                    {
+                        if let CppType::Named(name) = *element {
+                            let char_ty =
+                                CppType::Named(name.trim_start_matches("const ").to_string());
+                            if char_ty.is_wide_char() {
+                                let value =
+                                    decode_string_literal_spelling(&cursor_spelling(cursor));
+                                return ClangNodeKind::WideStringLiteral { value, char_ty };
+                            }
+                        }
+                    }
                     // Get the string value using evaluation
                     let eval = clang_sys::clang_Cursor_Evaluate(cursor);
                     let value = if !eval.is_null() {
@@ -1561,6 +1937,21 @@ impl ClangParser {
 
                 clang_sys::CXCursor_DeclRefExpr => {
                     let name = cursor_spelling(cursor);
+                    // Type trait variable templates (e.g. std::is_same_v<T, int>) are kept
+                    // as traits so they can be decided per template instantiation
+                    if let Some(trait_kind) = TypeTraitKind::from_variable_template(&name)
+                        .filter(|_| self.refers_to_std(cursor))
+                    {
+                        let type_args = self
+                            .get_template_arg_spellings(cursor)
+                            .iter()
+                            .map(|arg| self.parse_type_from_string(arg))
+                            .collect();
+                        return ClangNodeKind::TypeTraitExpr {
+                            trait_kind,
+                            type_args,
+                        };
+                    }
                     let ty = self.convert_type(clang_sys::clang_getCursorType(cursor));
                     let namespace_path = self.get_namespace_path(cursor);
                     ClangNodeKind::DeclRefExpr {
@@ -1595,6 +1986,15 @@ impl ClangParser {
                     ClangNodeKind::CallExpr { ty }
                 }
 
+                clang_sys::CXCursor_UnaryExpr => {
+                    if let Some(trait_expr) = self.try_parse_unary_type_trait(cursor) {
+                        trait_expr
+                    } else {
+                        let kind_spelling = clang_sys::clang_getCursorKindSpelling(kind);
+                        ClangNodeKind::Unknown(cx_string_to_string(kind_spelling))
+                    }
+                }
+
                 clang_sys::CXCursor_MemberRefExpr => {
                     let member_name = cursor_spelling(cursor);
                     let ty = self.convert_type(clang_sys::clang_getCursorType(cursor));
@@ -1712,13 +2112,14 @@ impl ClangParser {
                 // C++11 Lambda expressions
                 // CXCursor_LambdaExpr = 144
                 144 => {
-                    let (params, return_type, capture_default, captures) =
+                    let (params, return_type, capture_default, captures, init_captures) =
                         self.parse_lambda_info(cursor);
                     ClangNodeKind::LambdaExpr {
                         params,
                         return_type,
                         capture_default,
                         captures,
+                        init_captures,
                     }
                 }
 
@@ -1772,7 +2173,8 @@ impl ClangParser {
                 // CXCursor_NonTypeTemplateParameter = 28 - Non-type template parameter
                 28 => {
                     let name = cursor_spelling(cursor);
-                    ClangNodeKind::Unknown(format!("NonTypeTemplateParameter:{}", name))
+                    let ty = self.convert_type(clang_sys::clang_getCursorType(cursor));
+                    ClangNodeKind::NonTypeTemplateParmDecl { name, ty }
                 }
 
                 // CXCursor_MacroExpansion = 502 - Macro expansion
@@ -1841,6 +2243,9 @@ impl ClangParser {
                 clang_sys::CXType_ULong => CppType::Long { signed: false },
                 clang_sys::CXType_LongLong => CppType::LongLong { signed: true },
                 clang_sys::CXType_ULongLong => CppType::LongLong { signed: false },
+                // 128-bit integers have no dedicated variant; their names map to i128/u128
+                clang_sys::CXType_Int128 => CppType::Named("__int128".to_string()),
+                clang_sys::CXType_UInt128 => CppType::Named("unsigned __int128".to_string()),
                 clang_sys::CXType_Float => CppType::Float,
                 clang_sys::CXType_Double => CppType::Double,
 
@@ -1915,6 +2320,27 @@ impl ClangParser {
                     }
                 }
 
+                clang_sys::CXType_Auto => {
+                    // A deduced `auto` resolves to its canonical type, so `auto n = 5` is an
+                    // int and `auto p = &n` a pointer. Class types keep the deduced spelling
+                    // (e.g. "std::vector<int>" rather than the allocator-qualified form)
+                    let canonical = clang_sys::clang_getCanonicalType(ty);
+                    if canonical.kind != clang_sys::CXType_Invalid
+                        && canonical.kind != clang_sys::CXType_Auto
+                        && canonical.kind != clang_sys::CXType_Record
+                    {
+                        self.convert_type(canonical)
+                    } else {
+                        let spelling = clang_sys::clang_getTypeSpelling(ty);
+                        let name = cx_string_to_string(spelling);
+                        let name = name
+                            .trim_start_matches("struct ")
+                            .trim_start_matches("class ")
+                            .to_string();
+                        CppType::Named(name)
+                    }
+                }
+
                 clang_sys::CXType_FunctionProto => {
                     let return_type = clang_sys::clang_getResultType(ty);
                     let num_args = clang_sys::clang_getNumArgTypes(ty);
@@ -1932,6 +2358,22 @@ impl ClangParser {
                     }
                 }
 
+                clang_sys::CXType_MemberPointer => {
+                    let pointee = clang_sys::clang_getPointeeType(ty);
+                    if pointee.kind == clang_sys::CXType_FunctionProto {
+                        CppType::MemberPointer {
+                            class: Box::new(
+                                self.convert_type(clang_sys::clang_Type_getClassType(ty)),
+                            ),
+                            pointee: Box::new(self.convert_type(pointee)),
+                        }
+                    } else {
+                        // Data member pointers keep their spelling
+                        let spelling = clang_sys::clang_getTypeSpelling(ty);
+                        CppType::Named(cx_string_to_string(spelling))
+                    }
+                }
+
                 clang_sys::CXType_Typedef => {
                     // For typedef types, get the canonical (underlying) type
                     // This is important for recognizing typedef'd pointers (e.g., __type_name_t = const char*)
@@ -2138,7 +2580,7 @@ impl ClangParser {
     }
 
     /// Parse lambda expression information.
-    /// Returns (params, return_type, capture_default, captures).
+    /// Returns (params, return_type, capture_default, captures, init_captures).
     fn parse_lambda_info(
         &self,
         cursor: clang_sys::CXCursor,
@@ -2147,12 +2589,14 @@ impl ClangParser {
         CppType,
         CaptureDefault,
         Vec<(String, bool)>,
+        Vec<(String, usize)>,
     ) {
         unsafe {
             let mut params = Vec::new();
             let mut return_type = CppType::Void;
             let mut capture_default = CaptureDefault::None;
             let mut captures = Vec::new();
+            let mut init_captures = Vec::new();
 
             // Visit children to find parameters and body
             // Lambda structure: CXXRecordDecl (implicit class), then CompoundStmt (body)
@@ -2221,7 +2665,7 @@ impl ClangParser {
                 &mut visit_data as *mut _ as clang_sys::CXClientData,
             );
 
-            // Try to determine capture default from tokens
+            // Determine the capture default and explicit captures from the capture list tokens
             let tu = clang_sys::clang_Cursor_getTranslationUnit(cursor);
             let extent = clang_sys::clang_getCursorExtent(cursor);
             let mut tokens: *mut clang_sys::CXToken = ptr::null_mut();
@@ -2230,42 +2674,89 @@ impl ClangParser {
             clang_sys::clang_tokenize(tu, extent, &mut tokens, &mut num_tokens);
 
             if !tokens.is_null() && num_tokens > 0 {
-                // Look for [=] or [&] at the start
-                let mut found_bracket = false;
+                // Split the tokens between the leading [ and its matching ] into items
+                let mut items: Vec<Vec<String>> = Vec::new();
+                let mut current: Vec<String> = Vec::new();
+                let mut depth = 0usize;
                 for i in 0..num_tokens {
                     let token = *tokens.add(i as usize);
                     let spelling = clang_sys::clang_getTokenSpelling(tu, token);
                     let s = cx_string_to_string(spelling);
 
-                    if s == "[" {
-                        found_bracket = true;
-                    } else if found_bracket {
-                        if s == "=" {
-                            capture_default = CaptureDefault::ByCopy;
-                            break;
-                        } else if s == "&" && i + 1 < num_tokens {
-                            // Check if next token is ] for [&] default capture
-                            let next_token = *tokens.add((i + 1) as usize);
-                            let next_spelling = clang_sys::clang_getTokenSpelling(tu, next_token);
-                            let next_s = cx_string_to_string(next_spelling);
-                            if next_s == "]" {
-                                capture_default = CaptureDefault::ByRef;
+                    if depth == 0 {
+                        if s == "[" {
+                            depth = 1;
+                        }
+                        continue;
+                    }
+                    match s.as_str() {
+                        "[" | "(" | "{" => depth += 1,
+                        "]" | ")" | "}" => depth -= 1,
+                        _ => {}
+                    }
+                    if depth == 0 {
+                        break;
+                    }
+                    if depth == 1 && s == "," {
+                        items.push(std::mem::take(&mut current));
+                    } else {
+                        current.push(s);
+                    }
+                }
+                if !current.is_empty() {
+                    items.push(current);
+                }
+
+                // Explicit captures (everything but the capture default) are numbered in order
+                let explicit_items = items
+                    .iter()
+                    .filter(|item| !matches!(item.as_slice(), [d] if d == "=" || d == "&"));
+                for (index, item) in explicit_items.enumerate() {
+                    let init_name = match item.as_slice() {
+                        [amp, name, eq, ..] if amp == "&" && eq == "=" => Some(name),
+                        [name, eq, ..] if eq == "=" => Some(name),
+                        _ => None,
+                    };
+                    if let Some(name) = init_name {
+                        init_captures.push((name.clone(), index));
+                    }
+                }
+                for item in &items {
+                    match item.as_slice() {
+                        [eq] if eq == "=" => capture_default = CaptureDefault::ByCopy,
+                        [amp] if amp == "&" => capture_default = CaptureDefault::ByRef,
+                        // [this] / [*this] - not a local variable capture
+                        [this] | [_, this] if this == "this" => {}
+                        // [&x] or [&x = init]
+                        [amp, name, ..] if amp == "&" => {
+                            if let Some(c) = captures.iter_mut().find(|(n, _)| n == name) {
+                                c.1 = true;
+                            } else {
+                                captures.push((name.clone(), true));
+                            }
+                        }
+                        // [x] or [x = init]
+                        [name, ..] => {
+                            if let Some(c) = captures.iter_mut().find(|(n, _)| n == name) {
+                                c.1 = false;
+                            } else {
+                                captures.push((name.clone(), false));
                             }
-                            break;
-                        } else if s == "]" {
-                            // Empty capture []
-                            break;
-                        } else {
-                            // Some specific capture, not default
-                            break;
                         }
+                        [] => {}
                     }
                 }
 
                 clang_sys::clang_disposeTokens(tu, tokens, num_tokens);
             }
 
-            (params, return_type, capture_default, captures)
+            (
+                params,
+                return_type,
+                capture_default,
+                captures,
+                init_captures,
+            )
         }
     }
 
@@ -4075,6 +4566,89 @@ fn cursor_spelling(cursor: clang_sys::CXCursor) -> String {
     }
 }
 
+/// Join the tokens of a type back into its spelling, putting a space only between
+/// adjacent words (`unsigned int`, `std::string`, `const char*`).
+fn join_type_tokens(tokens: &[String]) -> String {
+    let is_word = |t: &str| t.chars().all(|c| c.is_alphanumeric() || c == '_');
+    let mut out = String::new();
+    let mut prev_is_word = false;
+    for token in tokens {
+        let token_is_word = is_word(token);
+        if prev_is_word && token_is_word {
+            out.push(' ');
+        }
+        out.push_str(token);
+        prev_is_word = token_is_word;
+    }
+    out
+}
+
+/// Decode the spelling of a string literal (e.g. `L"a\tb"`) into its text, dropping
+/// the encoding prefix and quotes and resolving escape sequences.
+fn decode_string_literal_spelling(spelling: &str) -> String {
+    let body = match (spelling.find('"'), spelling.rfind('"')) {
+        (Some(start), Some(end)) if end > start => &spelling[start + 1..end],
+        _ => spelling,
+    };
+    let mut out = String::new();
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        let Some(esc) = chars.next() else {
+            break;
+        };
+        let code = match esc {
+            'n' => '\n' as u32,
+            't' => '\t' as u32,
+            'r' => '\r' as u32,
+            'a' => 0x07,
+            'b' => 0x08,
+            'f' => 0x0c,
+            'v' => 0x0b,
+            'x' | 'u' | 'U' => {
+                let max = match esc {
+                    'u' => 4,
+                    'U' => 8,
+                    _ => usize::MAX,
+                };
+                let mut code = 0u32;
+                let mut len = 0;
+                while len < max {
+                    match chars.peek().and_then(|d| d.to_digit(16)) {
+                        Some(d) => {
+                            code = code.wrapping_mul(16).wrapping_add(d);
+                            chars.next();
+                            len += 1;
+                        }
+                        None => break,
+                    }
+                }
+                code
+            }
+            '0'..='7' => {
+                let mut code = esc.to_digit(8).unwrap_or(0);
+                for _ in 0..2 {
+                    match chars.peek().and_then(|d| d.to_digit(8)) {
+                        Some(d) => {
+                            code = code * 8 + d;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                code
+            }
+            // \\, \", \' and \? stand for themselves
+            other => other as u32,
+        };
+        out.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+    }
+    out
+}
+
 /// Get the mangled name of a cursor (for function declarations).
 ///
 /// Uses libclang's `clang_Cursor_getMangling` to get the platform-specific
@@ -4097,6 +4671,20 @@ fn cursor_mangled_name(cursor: clang_sys::CXCursor) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_join_type_tokens() {
+        let join = |tokens: &[&str]| {
+            join_type_tokens(&tokens.iter().map(|t| t.to_string()).collect::<Vec<_>>())
+        };
+        assert_eq!(join(&["std", "::", "string"]), "std::string");
+        assert_eq!(join(&["unsigned", "long", "long"]), "unsigned long long");
+        assert_eq!(join(&["const", "char", "*"]), "const char*");
+        assert_eq!(
+            join(&["std", "::", "vector", "<", "int", ">"]),
+            "std::vector<int>"
+        );
+    }
+
     #[test]
     fn test_parse_simple_function() {
         let parser = ClangParser::new().unwrap();
@@ -4717,6 +5305,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_wide_string_and_char_literals() {
+        let parser = ClangParser::new().unwrap();
+        let ast = parser
+            .parse_string(
+                r#"
+                const wchar_t* foo() {
+                    char16_t c = u'x';
+                    return L"tab\there";
+                }
+                "#,
+                "test.cpp",
+            )
+            .unwrap();
+
+        fn find<'a>(
+            node: &'a ClangNode,
+            pred: &dyn Fn(&ClangNode) -> bool,
+        ) -> Option<&'a ClangNode> {
+            if pred(node) {
+                return Some(node);
+            }
+            node.children.iter().find_map(|child| find(child, pred))
+        }
+
+        let literal = find(&ast.translation_unit, &|n| {
+            matches!(&n.kind, ClangNodeKind::WideStringLiteral { .. })
+        })
+        .expect("Expected WideStringLiteral");
+        if let ClangNodeKind::WideStringLiteral { value, char_ty } = &literal.kind {
+            assert_eq!(value, "tab\there");
+            assert_eq!(char_ty, &CppType::Named("wchar_t".to_string()));
+        }
+
+        let char_lit = find(&ast.translation_unit, &|n| {
+            matches!(&n.kind, ClangNodeKind::IntegerLiteral { value: 120, .. })
+        })
+        .expect("Expected character literal");
+        if let ClangNodeKind::IntegerLiteral { cpp_type, .. } = &char_lit.kind {
+            assert_eq!(cpp_type, &Some(CppType::Named("char16_t".to_string())));
+        }
+    }
+
     #[test]
     fn test_parse_lvalue_reference_parameter() {
         let parser = ClangParser::new().unwrap();
@@ -4908,6 +5539,8 @@ fn str_to_binary_op(s: &str) -> Option<BinaryOp> {
         "<<=" => Some(BinaryOp::ShlAssign),
         ">>=" => Some(BinaryOp::ShrAssign),
         "," => Some(BinaryOp::Comma),
+        ".*" => Some(BinaryOp::PtrMemD),
+        "->*" => Some(BinaryOp::PtrMemI),
         _ => None,
     }
 }
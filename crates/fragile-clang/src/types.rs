@@ -108,6 +108,13 @@ pub enum CppType {
         params: Vec<CppType>,
         is_variadic: bool,
     },
+    /// Pointer to member: R (C::*)(Args...) or T C::*
+    MemberPointer {
+        /// The class the member belongs to
+        class: Box<CppType>,
+        /// The member's type (a Function type for member function pointers)
+        pointee: Box<CppType>,
+    },
     /// Template parameter type (used in function/class templates).
     /// Represents a type that will be substituted during template instantiation.
     TemplateParam {
@@ -314,6 +321,10 @@ impl CppType {
                     }
                     // C standard I/O
                     "FILE" | "struct _IO_FILE" => "std::ffi::c_void".to_string(), // Opaque file handle
+                    // <csetjmp> jump buffer (jmp_buf is an array of one __jmp_buf_tag)
+                    "jmp_buf" | "__jmp_buf_tag" | "struct __jmp_buf_tag" => {
+                        "crate::fragile_runtime::JmpBuf".to_string()
+                    }
                     // nullptr_t type
                     "std::nullptr_t" | "nullptr_t" | "decltype(nullptr)" => {
                         "*mut std::ffi::c_void".to_string()
@@ -625,6 +636,26 @@ impl CppType {
                     return_type.to_rust_type_str()
                 )
             }
+            CppType::MemberPointer { class, pointee } => match pointee.as_ref() {
+                // Member function pointers take the object as an explicit first argument
+                CppType::Function {
+                    return_type,
+                    params,
+                    ..
+                } => {
+                    let params_str: Vec<_> =
+                        std::iter::once(format!("*mut {}", class.to_rust_type_str()))
+                            .chain(params.iter().map(|p| p.to_rust_type_str()))
+                            .collect();
+                    format!(
+                        "Option<fn({}) -> {}>",
+                        params_str.join(", "),
+                        return_type.to_rust_type_str()
+                    )
+                }
+                // Data member pointers are byte offsets into the object
+                _ => "isize".to_string(),
+            },
             CppType::TemplateParam { name, .. } => {
                 // Template parameters are represented by their name
                 // In Rust generics, this would be a generic type parameter
@@ -673,6 +704,9 @@ impl CppType {
                 params,
                 ..
             } => return_type.is_dependent() || params.iter().any(|p| p.is_dependent()),
+            CppType::MemberPointer { class, pointee } => {
+                class.is_dependent() || pointee.is_dependent()
+            }
             _ => false,
         }
     }
@@ -765,6 +799,10 @@ impl CppType {
                 params: params.iter().map(|p| p.substitute(substitutions)).collect(),
                 is_variadic: *is_variadic,
             },
+            CppType::MemberPointer { class, pointee } => CppType::MemberPointer {
+                class: Box::new(class.substitute(substitutions)),
+                pointee: Box::new(pointee.substitute(substitutions)),
+            },
             // Non-dependent types remain unchanged
             _ => self.clone(),
         }
@@ -893,6 +931,18 @@ impl CppType {
                 is_trivially_copyable: false,
                 is_trivially_destructible: true,
             }),
+
+            // Member pointers are scalars, but std::is_pointer is false for them
+            CppType::MemberPointer { .. } => Some(TypeProperties {
+                is_integral: false,
+                is_signed: false,
+                is_floating_point: false,
+                is_scalar: true,
+                is_pointer: false,
+                is_reference: false,
+                is_trivially_copyable: true,
+                is_trivially_destructible: true,
+            }),
         }
     }
 
@@ -916,6 +966,30 @@ impl CppType {
         self.properties().map(|p| p.is_floating_point)
     }
 
+    /// Check if this is one of the wide or unicode character types (`wchar_t`, `char8_t`,
+    /// `char16_t`, `char32_t`), which map to integers of their own width.
+    pub fn is_wide_char(&self) -> bool {
+        match self {
+            CppType::Named(name) => matches!(
+                name.trim_start_matches("const ").trim(),
+                "wchar_t" | "char8_t" | "char16_t" | "char32_t"
+            ),
+            _ => false,
+        }
+    }
+
+    /// Check if this is the `__int128` / `unsigned __int128` extension type (or its
+    /// `__int128_t` / `__uint128_t` typedef), which maps to `i128` / `u128`.
+    pub fn is_int128(&self) -> bool {
+        match self {
+            CppType::Named(name) => matches!(
+                name.trim_start_matches("const ").trim(),
+                "__int128" | "__int128_t" | "unsigned __int128" | "__uint128_t"
+            ),
+            _ => false,
+        }
+    }
+
     /// Check if this is an arithmetic type (integral or floating point).
     pub fn is_arithmetic(&self) -> Option<bool> {
         self.properties()
@@ -950,6 +1024,7 @@ impl CppType {
             | CppType::Array { .. }
             | CppType::Named(_)
             | CppType::Function { .. }
+            | CppType::MemberPointer { .. }
             | CppType::TemplateParam { .. }
             | CppType::DependentType { .. }
             | CppType::ParameterPack { .. } => None,
@@ -1182,6 +1257,18 @@ mod tests {
         assert_eq!(ref_.bit_width(), Some(64));
     }
 
+    #[test]
+    fn test_reference_type_mapping_respects_const() {
+        let int_ref = |is_const| CppType::Reference {
+            referent: Box::new(CppType::Int { signed: true }),
+            is_const,
+            is_rvalue: false,
+        };
+        // const int& can only be read through; int& allows writes
+        assert_eq!(int_ref(true).to_rust_type_str(), "&i32");
+        assert_eq!(int_ref(false).to_rust_type_str(), "&mut i32");
+    }
+
     #[test]
     fn test_bit_width_no_fixed_width() {
         // Void
@@ -1342,6 +1429,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_wide_char_type_mappings() {
+        let named = |name: &str| CppType::Named(name.to_string());
+        // wchar_t is a signed 32-bit integer on Linux
+        assert_eq!(named("wchar_t").to_rust_type_str(), "i32");
+        assert_eq!(named("char8_t").to_rust_type_str(), "u8");
+        assert_eq!(named("char16_t").to_rust_type_str(), "u16");
+        assert_eq!(named("char32_t").to_rust_type_str(), "u32");
+        assert_eq!(
+            CppType::Pointer {
+                pointee: Box::new(named("const char16_t")),
+                is_const: true,
+            }
+            .to_rust_type_str(),
+            "*const u16"
+        );
+
+        assert!(named("const wchar_t").is_wide_char());
+        assert!(named("char32_t").is_wide_char());
+        assert!(!CppType::Char { signed: true }.is_wide_char());
+        assert!(!named("wint_t").is_wide_char());
+    }
+
+    #[test]
+    fn test_int128_type_mappings() {
+        let named = |name: &str| CppType::Named(name.to_string());
+        assert_eq!(named("__int128").to_rust_type_str(), "i128");
+        assert_eq!(named("unsigned __int128").to_rust_type_str(), "u128");
+        assert_eq!(named("__uint128_t").to_rust_type_str(), "u128");
+        assert_eq!(
+            CppType::Array {
+                element: Box::new(named("unsigned __int128")),
+                size: Some(2),
+            }
+            .to_rust_type_str(),
+            "[u128; 2]"
+        );
+        assert_eq!(
+            CppType::Pointer {
+                pointee: Box::new(named("__int128")),
+                is_const: true,
+            }
+            .to_rust_type_str(),
+            "*const i128"
+        );
+
+        assert!(named("const unsigned __int128").is_int128());
+        assert!(!CppType::LongLong { signed: false }.is_int128());
+    }
+
     #[test]
     fn test_stream_type_mappings() {
         // NOTE: STL mappings removed - all types pass through as-is
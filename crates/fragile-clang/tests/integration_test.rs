@@ -51,6 +51,39 @@ fn test_generate_rust_code() {
     assert!(code.contains("return a + b"));
 }
 
+/// Test that parse errors carry structured diagnostics that serialize to JSON.
+#[test]
+fn test_parse_errors_as_json() {
+    let parser = ClangParser::new().expect("Failed to create parser");
+
+    let temp_dir = std::env::temp_dir().join("fragile_parse_error_tests");
+    std::fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+    let path = temp_dir.join("broken.cpp");
+    std::fs::write(&path, "int main() {\n    return undeclared_name;\n}\n")
+        .expect("Failed to write source");
+
+    let err = parser
+        .parse_file(&path)
+        .expect_err("broken file should not parse");
+    let errors = err
+        .downcast_ref::<fragile_clang::ClangErrors>()
+        .expect("parse errors should be ClangErrors");
+    assert_eq!(errors.diagnostics.len(), 1);
+
+    let json: serde_json::Value =
+        serde_json::from_str(&errors.diagnostics[0].to_json()).expect("invalid JSON");
+    assert_eq!(json["level"], "error");
+    assert!(json["message"]
+        .as_str()
+        .unwrap()
+        .contains("undeclared_name"));
+    assert!(json["file"].as_str().unwrap().ends_with("broken.cpp"));
+    assert_eq!(json["line"], 2);
+    assert_eq!(json["column"], 12);
+    // The span is the error's byte offset into the file
+    assert_eq!(json["span"]["offset"], 24);
+}
+
 /// Test generating stubs from C++ source.
 #[test]
 fn test_generate_stubs() {
@@ -187,6 +220,38 @@ fn test_while_loop() {
     assert!(code.contains("return sum"));
 }
 
+/// Test that Duff's device is reported as irreducible control flow instead of miscompiled.
+#[test]
+fn test_duffs_device_reported() {
+    let parser = ClangParser::new().expect("Failed to create parser");
+
+    let source = r#"
+        void copy(int* to, const int* from, int count) {
+            int n = (count + 3) / 4;
+            switch (count % 4) {
+            case 0: do { *to++ = *from++;
+            case 3:      *to++ = *from++;
+            case 2:      *to++ = *from++;
+            case 1:      *to++ = *from++;
+                    } while (--n > 0);
+            }
+        }
+    "#;
+
+    let ast = parser
+        .parse_string(source, "duff.cpp")
+        .expect("Failed to parse");
+    let code = AstCodeGen::new().generate(&ast.translation_unit);
+
+    // The first label inside the do-while is reported with its location
+    assert!(
+        code.contains("unsupported irreducible control flow: case label inside a loop nested in its switch at duff.cpp:6:13"),
+        "got:\n{}",
+        code
+    );
+    assert!(!code.contains("match count % 4"));
+}
+
 // ============================================================================
 // End-to-End Tests: Transpile -> Compile -> Run
 // ============================================================================
@@ -444,6 +509,42 @@ fn test_e2e_for_loop() {
     assert_eq!(exit_code, 0, "sum_for(10) should equal 55");
 }
 
+/// E2E test: Do-while loop runs its body before checking the condition
+#[test]
+fn test_e2e_do_while_loop() {
+    let source = r#"
+        int count_runs(int limit) {
+            int runs = 0;
+            do {
+                runs = runs + 1;
+                if (runs < limit) {
+                    continue;
+                }
+            } while (runs < limit);
+            return runs;
+        }
+
+        int main() {
+            // Condition is false immediately: the body still runs exactly once
+            if (count_runs(0) != 1) {
+                return 1;
+            }
+            if (count_runs(4) != 4) {
+                return 2;
+            }
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_do_while.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "do-while body should run once when the condition is false"
+    );
+}
+
 /// E2E test: Struct with methods
 #[test]
 fn test_e2e_struct_methods() {
@@ -479,6 +580,112 @@ fn test_e2e_struct_methods() {
     assert_eq!(exit_code, 0, "Counter should increment to 3");
 }
 
+/// E2E test: bit fields packed into one byte are read and written through accessors
+#[test]
+fn test_e2e_bit_field_accessors() {
+    let source = r#"
+        struct Flags {
+            unsigned int ready : 1;
+            unsigned int mode : 3;
+            unsigned int level : 4;
+        };
+
+        void bump(Flags* f) {
+            f->level += 2;
+        }
+
+        int main() {
+            Flags f;
+            f.ready = 1;
+            f.mode = 5;
+            f.level = 9;
+            bump(&f);
+            if (f.ready != 1) return 2;
+            if (f.mode != 5) return 3;
+            if (f.level != 11) return 4;
+            f.mode = 9; // truncated to 3 bits
+            if (f.mode != 1) return 5;
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_bit_field_accessors.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "bit field accessors should pack and unpack values"
+    );
+}
+
+/// E2E test: structured bindings over a struct, by value and by reference
+#[test]
+fn test_e2e_structured_bindings() {
+    let source = r#"
+        struct Point {
+            int x;
+            int y;
+        };
+
+        int main() {
+            Point p;
+            p.x = 3;
+            p.y = 4;
+            auto [a, b] = p;
+            a = 10; // copy: p is untouched
+            if (p.x != 3) return 1;
+            auto& [rx, ry] = p;
+            rx += b;
+            ry = a;
+            if (p.x != 7) return 2;
+            if (p.y != 10) return 3;
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_structured_bindings.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "structured bindings should destructure struct fields"
+    );
+}
+
+/// E2E test: C++20 synthesizes `!=` from a class's only `operator==`
+#[test]
+fn test_e2e_synthesized_not_equal() {
+    let source = r#"
+        struct Version {
+            int major;
+            int minor;
+            bool operator==(const Version& other) const {
+                return major == other.major && minor == other.minor;
+            }
+        };
+
+        int main() {
+            Version a;
+            a.major = 1;
+            a.minor = 2;
+            Version b = a;
+            if (a != b) return 1;
+            b.minor = 3;
+            if (!(a != b)) return 2;
+            if (a == b) return 3;
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_synthesized_not_equal.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "!= should be synthesized from operator== alone"
+    );
+}
+
 /// E2E test: Arrays
 #[test]
 fn test_e2e_arrays() {
@@ -601,6 +808,39 @@ fn test_e2e_nested_control() {
     assert_eq!(exit_code, 0, "There should be 8 primes between 1 and 20");
 }
 
+/// E2E test: Ternary conditional operator as an initializer and as an argument
+#[test]
+fn test_e2e_ternary_operator() {
+    let source = r#"
+        int twice(int x) { return x * 2; }
+
+        int main() {
+            int a = 3;
+            int b = 7;
+            int m = a > b ? a : b;
+            if (m != 7) return 1;
+
+            long long big = 10;
+            long long r = a > 0 ? 1 : big;
+            if (r != 1) return 2;
+
+            if (twice(a < b ? a : b) != 6) return 3;
+
+            int sign = a > b ? 1 : a < b ? -1 : 0;
+            if (sign != -1) return 4;
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_ternary.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Ternary expressions should select the correct branch"
+    );
+}
+
 /// E2E test: Constructor
 #[test]
 fn test_e2e_constructor() {
@@ -634,762 +874,2397 @@ fn test_e2e_constructor() {
     assert_eq!(exit_code, 0, "Point(3,4).distance_sq() should be 25");
 }
 
-/// E2E test: nullptr handling
+/// E2E test: delegating constructors call the target constructor
 #[test]
-fn test_e2e_nullptr() {
+fn test_e2e_delegating_constructor() {
     let source = r#"
-        int* get_null() {
-            return nullptr;
-        }
+        struct Counter {
+            int count;
+            int step;
+
+            Counter() : Counter(10) {}
+            Counter(int start) : count(start), step(1) {}
+            Counter(int start, int s) : Counter(start) {
+                step = s;
+            }
+
+            void tick() { count += step; }
+        };
 
         int main() {
-            int* p = nullptr;
-            if (p == nullptr) {
-                return 0;
+            Counter a;
+            if (a.count != 10 || a.step != 1) return 1;
+            a.tick();
+            Counter b(5, 3);
+            b.tick();
+            if (b.count != 8) return 2;
+            return a.count == 11 ? 0 : 3;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_delegating_constructor.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "delegating constructors should run the target constructor"
+    );
+}
+
+/// E2E test: conversion operators to a primitive and to another class
+#[test]
+fn test_e2e_conversion_operators() {
+    let source = r#"
+        struct Meters {
+            double value;
+        };
+
+        struct Feet {
+            double value;
+            operator Meters() const {
+                Meters m;
+                m.value = value * 0.3048;
+                return m;
             }
-            return 1;
+        };
+
+        struct Wrapper {
+            int value;
+            operator int() const { return value; }
+        };
+
+        int twice(int x) { return x * 2; }
+
+        int main() {
+            Wrapper w;
+            w.value = 21;
+            int n = w;
+            if (n != 21) return 1;
+            if (twice(w) != 42) return 2;
+            Feet f;
+            f.value = 10.0;
+            Meters m = f;
+            if (m.value < 3.04 || m.value > 3.05) return 3;
+            return 0;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_nullptr.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_conversion_operators.cpp").expect("E2E test failed");
 
-    assert_eq!(exit_code, 0, "nullptr should be handled correctly");
+    assert_eq!(
+        exit_code, 0,
+        "implicit conversions should call the conversion operator"
+    );
 }
 
-/// E2E test: C++ casts (static_cast, reinterpret_cast)
+/// E2E test: member and free `operator<<` on class types lower to `op_shl`
 #[test]
-fn test_e2e_casts() {
+fn test_e2e_shift_operator_overloads() {
     let source = r#"
-        int test_static_cast(double d) {
-            return static_cast<int>(d);
+        struct BitSet {
+            unsigned bits;
+            BitSet& operator<<(int n) {
+                bits = bits << n;
+                return *this;
+            }
+        };
+
+        struct Mask {
+            unsigned bits;
+        };
+
+        Mask& operator<<(Mask& m, int n) {
+            m.bits = m.bits | (1u << n);
+            return m;
         }
 
         int main() {
-            double d = 3.7;
-            int i = static_cast<int>(d);
-            if (i == 3) {
-                return 0;
-            }
-            return 1;
+            BitSet b;
+            b.bits = 1;
+            b << 3;
+            if (b.bits != 8) return 1;
+            b << 1 << 2;
+            if (b.bits != 64) return 2;
+            Mask m;
+            m.bits = 0;
+            m << 0 << 4;
+            if (m.bits != 17) return 3;
+            int x = 5;
+            if ((x << 2) != 20) return 4;
+            return 0;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_casts.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_shift_operator_overloads.cpp").expect("E2E test failed");
 
-    assert_eq!(exit_code, 0, "static_cast<int>(3.7) should equal 3");
+    assert_eq!(exit_code, 0, "class operator<< should dispatch to op_shl");
 }
 
-/// E2E test: new/delete
+/// E2E test: member `swap` and ADL `swap(a, b)` both call the class's swap method
 #[test]
-fn test_e2e_new_delete() {
+fn test_e2e_member_swap() {
     let source = r#"
-        int main() {
-            int* p = new int(42);
-            int v = *p;
-            delete p;
-            if (v == 42) {
-                return 0;
+        struct Stack {
+            int items[4];
+            int size;
+            int swaps;
+            void swap(Stack& other) {
+                for (int i = 0; i < 4; i++) {
+                    int t = items[i];
+                    items[i] = other.items[i];
+                    other.items[i] = t;
+                }
+                int s = size;
+                size = other.size;
+                other.size = s;
+                swaps = swaps + 1;
             }
-            return 1;
+        };
+
+        void swap(Stack& a, Stack& b) { a.swap(b); }
+
+        int main() {
+            Stack a;
+            a.size = 1;
+            a.items[0] = 7;
+            a.swaps = 0;
+            Stack b;
+            b.size = 2;
+            b.items[0] = 9;
+            b.swaps = 0;
+            a.swap(b);
+            if (a.size != 2 || a.items[0] != 9) return 1;
+            swap(a, b);
+            if (a.size != 1 || b.items[0] != 9) return 2;
+            if (a.swaps != 2) return 3;
+            return 0;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_new_delete.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_member_swap.cpp").expect("E2E test failed");
 
-    assert_eq!(exit_code, 0, "new int(42) should create value 42");
+    assert_eq!(exit_code, 0, "swap calls should use the member swap");
 }
 
-/// E2E test: new[]/delete[] (array allocation)
+/// E2E test: a thread_local counter is stored in thread_local! and updated through its Cell
 #[test]
-fn test_e2e_array_new_delete() {
+fn test_e2e_thread_local_counter() {
     let source = r#"
+        thread_local int counter = 10;
+
+        int next_id() {
+            counter++;
+            counter += 2;
+            return counter;
+        }
+
         int main() {
-            int* arr = new int[5];
-            arr[0] = 10;
-            arr[1] = 20;
-            int sum = arr[0] + arr[1];
-            delete[] arr;
-            if (sum == 30) {
-                return 0;
-            }
-            return 1;
+            if (next_id() != 13) return 1;
+            if (next_id() != 16) return 2;
+            counter = 0;
+            if (++counter != 1) return 3;
+            return 0;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_array_new.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_thread_local_counter.cpp").expect("E2E test failed");
 
-    assert_eq!(
-        exit_code, 0,
-        "new int[5] should create array that can be indexed"
-    );
+    assert_eq!(exit_code, 0, "thread_local counter should keep its value");
 }
 
-/// E2E test: Single inheritance
+/// E2E test: new on an alignas type returns suitably aligned storage
 #[test]
-fn test_e2e_inheritance() {
+fn test_e2e_new_over_aligned_type() {
     let source = r#"
-        struct Animal {
-            int legs;
-        };
-
-        struct Dog : public Animal {
-            int tail;
+        struct alignas(64) Block {
+            int v;
         };
 
         int main() {
-            Dog d;
-            d.legs = 4;  // Inherited from Animal
-            d.tail = 1;
-            if (d.legs == 4 && d.tail == 1) {
-                return 0;
-            }
-            return 1;
+            Block* b = new Block();
+            b->v = 7;
+            if ((unsigned long)b % 64 != 0) return 1;
+            int v = b->v;
+            delete b;
+            return v == 7 ? 0 : 2;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_inheritance.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_new_over_aligned.cpp").expect("E2E test failed");
 
     assert_eq!(
         exit_code, 0,
-        "Inheritance should embed base struct as __base field"
+        "new alignas(64) Block should be 64-byte aligned"
     );
 }
 
-/// E2E test: Destructor → Drop trait
+/// E2E test: delete[] runs every element destructor in reverse order
 #[test]
-fn test_e2e_destructor() {
+fn test_e2e_array_new_delete_destructors() {
     let source = r#"
-        struct Resource {
-            int value;
-
-            Resource() {
-                value = 100;
-            }
-
-            ~Resource() {
-                // Destructor body - this code runs when Drop::drop is called
-                value = 0;  // Reset value on destruction
-            }
+        int destroyed = 0;
+        int order = 0;
 
-            int get() {
-                return value;
+        struct Tracked {
+            int id;
+            ~Tracked() {
+                destroyed++;
+                order = order * 10 + id;
             }
         };
 
         int main() {
-            Resource r;
-            int v = r.get();
-            if (v == 100) {
-                return 0;
+            Tracked* items = new Tracked[3];
+            for (int i = 0; i < 3; i++) {
+                items[i].id = i + 1;
             }
-            return 1;
+            delete[] items;
+            if (destroyed != 3) return 1;
+            if (order != 321) return 2;
+            return 0;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_destructor.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_array_new_delete.cpp").expect("E2E test failed");
 
     assert_eq!(
         exit_code, 0,
-        "Struct with destructor should compile and run"
+        "delete[] should destroy all elements in reverse order"
     );
 }
 
+/// E2E test: std::function reassignment and emptiness checks
 #[test]
-fn test_e2e_copy_constructor() {
+fn test_e2e_std_function_assignment() {
     let source = r#"
-        struct Point {
-            int x;
-            int y;
+        #include <functional>
 
-            Point() {
-                x = 0;
-                y = 0;
-            }
+        int triple(int x) {
+            return x * 3;
+        }
 
-            Point(int px, int py) {
-                x = px;
-                y = py;
+        int call_or(const std::function<int(int)>& fn, int v, int fallback) {
+            if (fn) {
+                return fn(v);
             }
+            return fallback;
+        }
 
-            // Copy constructor
-            Point(const Point& other) {
-                x = other.x;
-                y = other.y;
-            }
+        int main() {
+            std::function<int(int)> fn;
+            if (fn != nullptr) return 1;
+            if (call_or(fn, 2, -1) != -1) return 2;
 
-            int sum() {
-                return x + y;
-            }
-        };
+            fn = [](int x) { return x + 1; };
+            if (fn == nullptr) return 3;
+            if (call_or(fn, 2, -1) != 3) return 4;
+
+            fn = triple;
+            if (fn(2) != 6) return 5;
+
+            fn = nullptr;
+            if (fn) return 6;
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_std_function_assignment.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "std::function should track reassignment and emptiness"
+    );
+}
+
+#[test]
+fn test_e2e_std_function_stateful_lambda() {
+    let source = r#"
+        #include <functional>
 
         int main() {
-            Point a(10, 20);
-            Point b = a;  // Uses copy constructor
-            // Verify both a and b have the same values
-            if (a.sum() == 30 && b.sum() == 30) {
-                return 0;
-            }
-            return 1;
+            int total = 0;
+            std::function<int(int)> add = [&total](int x) {
+                total += x;
+                return total;
+            };
+            add(2);
+            if (add(3) != 5) return 1;
+            std::function<int(int)> counter;
+            int calls = 0;
+            counter = [calls](int x) mutable { calls++; return calls * x; };
+            counter(1);
+            return counter(10) == 20 ? 0 : 2;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_copy_ctor.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_std_function_stateful.cpp").expect("E2E test failed");
 
     assert_eq!(
         exit_code, 0,
-        "Struct with copy constructor should compile and run"
+        "std::function should hold lambdas that mutate their captures"
     );
 }
 
 #[test]
-fn test_e2e_exception_handling() {
+fn test_e2e_index_loop_against_size() {
     let source = r#"
-        int divide(int a, int b) {
-            if (b == 0) {
-                throw "Division by zero";
+        #include <vector>
+        #include <cstddef>
+
+        int main() {
+            std::vector<int> v;
+            v.push_back(1);
+            v.push_back(2);
+            v.push_back(3);
+
+            int sum = 0;
+            for (int i = 0; i < v.size(); i++) {
+                sum += v[i];
             }
-            return a / b;
-        }
+            if (sum != 6) return 1;
 
-        int safe_divide(int a, int b) {
-            try {
-                return divide(a, b);
-            } catch (...) {
-                return -1;
+            size_t count = 0;
+            for (size_t j = 0; j <= v.size() - 1; j++) {
+                count++;
             }
+            if (count != 3) return 2;
+            return 0;
         }
+    "#;
 
-        int main() {
-            // Test normal division
-            int r1 = safe_divide(10, 2);
-            if (r1 != 5) return 1;
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_index_loop_against_size.cpp").expect("E2E test failed");
 
-            // Test division by zero (should catch and return -1)
-            int r2 = safe_divide(10, 0);
-            if (r2 != -1) return 2;
+    assert_eq!(
+        exit_code, 0,
+        "Index loops bounded by size() should compile and run"
+    );
+}
+
+#[test]
+fn test_e2e_map_index_counts() {
+    let source = r#"
+        #include <map>
+        #include <string>
 
+        int main() {
+            std::map<std::string, int> counts;
+            const char* words[] = {"apple", "pear", "apple", "fig", "apple"};
+            for (int i = 0; i < 5; i++) {
+                std::string word = words[i];
+                counts[word]++;
+            }
+            if (counts.size() != 3) return 1;
+            if (counts["apple"] != 3) return 2;
+            if (counts["pear"] != 1) return 3;
+            // Reading a missing key inserts a default value
+            if (counts["kiwi"] != 0) return 4;
+            if (counts.size() != 4) return 5;
             return 0;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_exception.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_map_index_counts.cpp").expect("E2E test failed");
 
-    assert_eq!(exit_code, 0, "Exception handling should compile and run");
+    assert_eq!(
+        exit_code, 0,
+        "std::map operator[] should insert a default value for missing keys"
+    );
 }
 
 #[test]
-fn test_e2e_namespaces() {
+fn test_e2e_map_initializer_list() {
     let source = r#"
-        namespace math {
-            int add(int a, int b) {
-                return a + b;
-            }
+        #include <map>
+        #include <string>
 
-            namespace utils {
-                int multiply(int a, int b) {
-                    return a * b;
-                }
+        int main() {
+            std::map<int, int> squares = {{3, 9}, {1, 1}, {2, 4}};
+            if (squares.size() != 3) return 1;
+            if (squares[2] != 4) return 2;
+            // Duplicate keys keep the first value, like repeated insert()
+            std::map<std::string, int> ages{{"ann", 31}, {"bob", 27}, {"ann", 99}};
+            if (ages.size() != 2) return 3;
+            if (ages["ann"] != 31) return 4;
+            return ages["bob"] == 27 ? 0 : 5;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_map_initializer_list.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "A std::map built from a braced list of pairs should contain those pairs"
+    );
+}
+
+#[test]
+fn test_e2e_switch_default_first() {
+    let source = r#"
+        int classify(int x) {
+            int r = 0;
+            switch (x) {
+                default:
+                    r = 100;
+                case 1:
+                    r += 1;
+                    break;
+                case 2:
+                    r = 2;
+                    break;
             }
+            return r;
         }
 
         int main() {
-            int r1 = math::add(2, 3);
-            int r2 = math::utils::multiply(4, 5);
-            if (r1 == 5 && r2 == 20) {
-                return 0;
-            }
-            return 1;
+            if (classify(1) != 1) return 1;
+            if (classify(2) != 2) return 2;
+            // Unmatched values take the leading default, then fall through into case 1
+            return classify(7) == 101 ? 0 : 3;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_namespace.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_switch_default_first.cpp").expect("E2E test failed");
 
-    assert_eq!(exit_code, 0, "Namespace functions should compile and run");
+    assert_eq!(
+        exit_code, 0,
+        "A default listed first should still catch unmatched values"
+    );
 }
 
-/// Test virtual method override resolution (static dispatch).
 #[test]
-fn test_e2e_virtual_override() {
+fn test_e2e_vector_bool_flip_and_xor() {
     let source = r#"
-        class Animal {
-        public:
-            virtual int speak() {
-                return 1;
-            }
-            int eat() {
-                return 10;
+        #include <vector>
+
+        std::vector<bool> operator^(const std::vector<bool>& a, const std::vector<bool>& b) {
+            std::vector<bool> out;
+            for (size_t i = 0; i < a.size(); i++) {
+                out.push_back(a[i] != b[i]);
             }
-        };
+            return out;
+        }
 
-        class Dog : public Animal {
-        public:
-            int speak() override {
-                return 2;
+        int count_set(const std::vector<bool>& v) {
+            int n = 0;
+            for (size_t i = 0; i < v.size(); i++) {
+                if (v[i]) n++;
             }
-        };
+            return n;
+        }
 
         int main() {
-            Dog d;
-            int a = d.speak();      // 2 (overridden)
-            int b = d.eat();        // 10 (inherited)
-            if (a == 2 && b == 10) {
-                return 0;
+            std::vector<bool> a;
+            std::vector<bool> b;
+            for (int i = 0; i < 10; i++) {
+                a.push_back(i % 2 == 0);
+                b.push_back(i % 3 == 0);
             }
-            return 1;
+            // {0,2,4,6,8} ^ {0,3,6,9} = {2,3,4,8,9}
+            if (count_set(a ^ b) != 5) return 1;
+            a.flip();
+            if (count_set(a) != 5) return 2;
+            a[0].flip();
+            return count_set(a) == 6 ? 0 : 3;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_virtual_override.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_vector_bool_flip.cpp").expect("E2E test failed");
 
     assert_eq!(
         exit_code, 0,
-        "Virtual method override should work correctly"
+        "vector<bool> flip and XOR should count bits correctly"
     );
 }
 
-/// Test base class constructor delegation.
 #[test]
-fn test_e2e_base_constructor() {
+fn test_e2e_accumulate_custom_operation() {
     let source = r#"
-        class Base {
-        protected:
-            int x;
-            int y;
-        public:
-            Base(int a, int b) : x(a), y(b) {}
-        };
-
-        class Derived : public Base {
-            int z;
-        public:
-            Derived(int a, int b, int c) : Base(a, b), z(c) {}
-            int sum() { return x + y + z; }
-        };
+        #include <functional>
+        #include <numeric>
+        #include <vector>
 
         int main() {
-            Derived d(10, 20, 30);
-            if (d.sum() == 60) {
-                return 0;
+            std::vector<int> v;
+            for (int i = 1; i <= 5; i++) {
+                v.push_back(i);
             }
-            return 1;
+            int product = std::accumulate(v.begin(), v.end(), 1, std::multiplies<int>());
+            if (product != 120) return 1;
+            int sum = std::accumulate(v.begin(), v.end(), 0);
+            if (sum != 15) return 2;
+            int max = std::accumulate(v.begin(), v.end(), 0, [](int a, int b) { return a > b ? a : b; });
+            return max == 5 ? 0 : 3;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_base_constructor.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_accumulate_op.cpp").expect("E2E test failed");
 
     assert_eq!(
         exit_code, 0,
-        "Base class constructor delegation should work"
+        "std::accumulate should fold with the given operation"
     );
 }
 
-/// Test operator overloading.
 #[test]
-fn test_e2e_operator_overloading() {
+fn test_e2e_sort_with_std_greater() {
     let source = r#"
-        class Point {
-            int x;
-            int y;
-        public:
-            Point(int a, int b) : x(a), y(b) {}
-            Point operator+(const Point& other) const {
-                return Point(x + other.x, y + other.y);
-            }
-            bool operator==(const Point& other) const {
-                return x == other.x && y == other.y;
-            }
-            int getX() const { return x; }
-            int getY() const { return y; }
-        };
+        #include <algorithm>
+        #include <functional>
+        #include <vector>
 
         int main() {
-            Point a(1, 2);
-            Point b(3, 4);
-            Point c = a + b;
-            if (c.getX() == 4 && c.getY() == 6) {
-                return 0;
-            }
-            return 1;
+            std::vector<int> v;
+            v.push_back(3);
+            v.push_back(1);
+            v.push_back(2);
+            std::sort(v.begin(), v.end(), std::greater<int>());
+            if (v[0] != 3 || v[1] != 2 || v[2] != 1) return 1;
+            std::sort(v.begin(), v.end());
+            if (v[0] != 1 || v[2] != 3) return 2;
+            auto add = std::plus<int>();
+            return add(v[0], v[1]) == 3 ? 0 : 3;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_operator_overloading.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_sort_greater.cpp").expect("E2E test failed");
 
-    assert_eq!(exit_code, 0, "Operator overloading should work correctly");
+    assert_eq!(
+        exit_code, 0,
+        "std::sort with std::greater should sort in descending order"
+    );
 }
 
-/// Test dynamic dispatch (virtual method polymorphism).
 #[test]
-fn test_e2e_dynamic_dispatch() {
+fn test_e2e_expected_has_value() {
     let source = r#"
-        class Animal {
-        public:
-            virtual int speak() { return 1; }
-        };
+        #include <expected>
 
-        class Dog : public Animal {
-        public:
-            int speak() override { return 2; }
-        };
-
-        class Cat : public Animal {
-        public:
-            int speak() override { return 3; }
-        };
+        enum class ErrorCode { Negative, TooLarge };
 
-        int callSpeak(Animal* a) {
-            return a->speak();
+        std::expected<int, ErrorCode> parse(int x) {
+            if (x < 0) return std::unexpected(ErrorCode::Negative);
+            if (x > 100) return std::unexpected(ErrorCode::TooLarge);
+            return x * 2;
         }
 
         int main() {
-            Dog d;
-            Cat c;
-            // Dynamic dispatch: should call Dog::speak() and Cat::speak()
-            int result = callSpeak(&d) + callSpeak(&c);
-            if (result == 5) {
-                return 0;
-            }
-            return 1;
+            auto ok = parse(21);
+            if (!ok.has_value() || ok.value() != 42) return 1;
+            auto bad = parse(-1);
+            if (bad.has_value()) return 2;
+            if (bad.error() != ErrorCode::Negative) return 3;
+            if (parse(500).value_or(7) != 7) return 4;
+            return ok ? 0 : 5;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_dynamic_dispatch.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_expected.cpp").expect("E2E test failed");
 
     assert_eq!(
         exit_code, 0,
-        "Dynamic dispatch should correctly call derived class methods"
+        "std::expected should hold either the value or the error"
     );
 }
 
-/// Test deep inheritance hierarchy (A → B → C → D) with virtual methods.
-/// Verifies vtable dispatch works through multiple inheritance levels.
 #[test]
-fn test_e2e_deep_inheritance() {
+fn test_e2e_static_method_calls() {
     let source = r#"
-        // Root class with virtual method
-        class Base {
-        public:
-            virtual int level() { return 0; }
+        struct Counter {
+            int value;
+            static int created;
+
+            static Counter make(int start) {
+                created++;
+                Counter c;
+                c.value = start;
+                return c;
+            }
+            static int twice(int x) { return x * 2; }
         };
 
-        // Level 1 - overrides Base::level
-        class Level1 : public Base {
-        public:
-            int level() override { return 1; }
-        };
+        int Counter::created = 0;
 
-        // Level 2 - overrides Level1::level
-        class Level2 : public Level1 {
-        public:
-            int level() override { return 2; }
-        };
+        int main() {
+            Counter a = Counter::make(3);
+            Counter b = a.make(4);
+            if (a.value != 3 || b.value != 4) return 1;
+            if (Counter::twice(5) != 10 || b.twice(6) != 12) return 2;
+            return Counter::created == 2 ? 0 : 3;
+        }
+    "#;
 
-        // Level 3 - overrides Level2::level
-        class Level3 : public Level2 {
-        public:
-            int level() override { return 3; }
-        };
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_static_method.cpp").expect("E2E test failed");
 
-        // Level 4 - does NOT override, inherits Level3::level
-        class Level4 : public Level3 {
-        public:
-            // No override - should still return 3
-        };
+    assert_eq!(
+        exit_code, 0,
+        "Static methods should be callable through the class name and an object"
+    );
+}
 
-        int getLevel(Base* b) {
-            return b->level();
-        }
+#[test]
+fn test_e2e_map_insert_ordered_iteration() {
+    let source = r#"
+        #include <map>
 
         int main() {
-            Base b;
-            Level1 l1;
-            Level2 l2;
-            Level3 l3;
-            Level4 l4;
-
-            // Test virtual dispatch at each level
-            int sum = getLevel(&b) + getLevel(&l1) + getLevel(&l2) + getLevel(&l3) + getLevel(&l4);
-            // Expected: 0 + 1 + 2 + 3 + 3 = 9
-            if (sum == 9) {
-                return 0;
-            }
-            return 1;
+            std::map<int, int> m;
+            m.insert({3, 30});
+            m.insert({1, 10});
+            m.insert({2, 20});
+            m.insert({1, 99});
+            if (m.erase(2) != 1 || m.count(2) != 0) return 1;
+            int keys = 0;
+            for (const auto& e : m) keys = keys * 10 + e.first;
+            if (keys != 13) return 2;
+            return m[1] == 10 && m.size() == 2 ? 0 : 3;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_deep_inheritance.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_map_insert.cpp").expect("E2E test failed");
 
     assert_eq!(
         exit_code, 0,
-        "Deep inheritance virtual dispatch should work correctly"
+        "std::map should keep existing entries on insert and iterate in key order"
     );
 }
 
-/// Test that RTTI type IDs are generated correctly for vtables.
-/// Verifies the RTTI infrastructure exists without testing dynamic_cast directly.
 #[test]
-fn test_e2e_vtable_rtti() {
+fn test_e2e_string_replace_insert() {
     let source = r#"
-        class Animal {
-        public:
-            virtual int id() { return 1; }
-            virtual ~Animal() {}
-        };
-
-        class Dog : public Animal {
-        public:
-            int id() override { return 2; }
-        };
-
-        class Cat : public Animal {
-        public:
-            int id() override { return 3; }
-        };
+        #include <string>
 
         int main() {
-            Animal a;
-            Dog d;
-            Cat c;
-
-            // Test that virtual dispatch works correctly
-            Animal* pa = &a;
-            Animal* pd = &d;
-            Animal* pc = &c;
-
-            int sum = pa->id() + pd->id() + pc->id();
-            // Expected: 1 + 2 + 3 = 6
-            if (sum == 6) {
-                return 0;  // Success
-            }
-            return 1;
+            std::string s = "hello world";
+            s.replace(0, 5, "howdy");
+            s.insert(5, ",");
+            if (s != "howdy, world") return 1;
+            s.replace(s.find("world"), 100, "there");
+            return s == "howdy, there" ? 0 : 2;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_vtable_rtti.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_string_replace.cpp").expect("E2E test failed");
 
     assert_eq!(
         exit_code, 0,
-        "RTTI type IDs should be generated for vtables"
+        "std::string::replace and insert should modify the string in place"
     );
 }
 
-/// Test dynamic_cast with RTTI.
-/// Verifies that dynamic_cast correctly returns the pointer on success
-/// and nullptr on failure.
 #[test]
-fn test_e2e_dynamic_cast() {
+fn test_e2e_string_index_substr_find() {
     let source = r#"
-        class Base {
-        public:
-            virtual int id() { return 1; }
-            virtual ~Base() {}
-        };
-
-        class Derived : public Base {
-        public:
-            int id() override { return 2; }
-        };
+        #include <string>
 
         int main() {
-            Derived d;
-            Base b;
+            std::string s = "key=value";
+            if (s[0] != 'k' || s[3] != '=') return 1;
+            std::string::size_type eq = s.find("=");
+            if (eq != 3) return 2;
+            if (s.find("missing") != std::string::npos) return 3;
+            std::string key = s.substr(0, eq);
+            std::string value = s.substr(eq + 1, 100);
+            if (key != "key") return 4;
+            return value == "value" ? 0 : 5;
+        }
+    "#;
 
-            Base* pd = &d;  // Points to Derived
-            Base* pb = &b;  // Points to Base
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_string_index_substr.cpp").expect("E2E test failed");
 
-            // Test 1: dynamic_cast should succeed when actual type matches
-            Derived* d1 = dynamic_cast<Derived*>(pd);
-            if (d1 == nullptr) return 1;  // Should not be null
+    assert_eq!(
+        exit_code, 0,
+        "std::string indexing, substr and find should follow C++ semantics"
+    );
+}
 
-            // Test 2: dynamic_cast should fail when actual type doesn't match
-            Derived* d2 = dynamic_cast<Derived*>(pb);
-            if (d2 != nullptr) return 3;  // Should be null
+#[test]
+fn test_e2e_range_for_over_temporary() {
+    let source = r#"
+        #include <vector>
 
-            // Test 3: dynamic_cast on nullptr should return nullptr
-            Base* null_ptr = nullptr;
-            Derived* d3 = dynamic_cast<Derived*>(null_ptr);
-            if (d3 != nullptr) return 4;  // Should be null
+        std::vector<int> makeVector() {
+            std::vector<int> v;
+            for (int i = 1; i <= 4; i++) v.push_back(i * 10);
+            return v;
+        }
 
-            return 0;  // All tests passed
+        int main() {
+            int sum = 0;
+            for (int x : makeVector()) sum += x;
+            for (const int& y : makeVector()) sum += y;
+            return sum == 200 ? 0 : 1;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_dynamic_cast.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_range_for_temporary.cpp").expect("E2E test failed");
 
-    assert_eq!(exit_code, 0, "dynamic_cast should work correctly with RTTI");
+    assert_eq!(
+        exit_code, 0,
+        "A temporary range should live until the loop finishes"
+    );
 }
 
-/// Test function template instantiation.
-/// Verifies that function templates are correctly instantiated with concrete types.
 #[test]
-fn test_e2e_function_template() {
+fn test_e2e_std_array_at_and_size() {
     let source = r#"
-        template<typename T>
-        T add(T a, T b) {
-            return a + b;
-        }
+        #include <array>
 
-        template<typename T>
-        T identity(T x) {
-            return x;
+        int main() {
+            std::array<int, 3> a = {4, 5, 6};
+            a.at(0) = 1;
+            int sum = 0;
+            for (int i = 0; i < (int)a.size(); i++) sum += a[i];
+            if (sum != 12) return 1;
+            return a.at(2) == 6 && a.data()[1] == 5 ? 0 : 2;
         }
+    "#;
 
-        int main() {
-            // Test 1: int instantiation of add
-            int sum = add(3, 4);
-            if (sum != 7) return 1;
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_std_array_at.cpp").expect("E2E test failed");
 
-            // Test 2: identity function template
-            int x = identity(42);
-            if (x != 42) return 2;
+    assert_eq!(
+        exit_code, 0,
+        "std::array indexing, at() and size() should match C++"
+    );
+}
 
-            return 0;  // All tests passed
+#[test]
+fn test_e2e_numeric_limits_min_tracker() {
+    let source = r#"
+        #include <limits>
+
+        int main() {
+            int values[] = {7, -3, 12, 5};
+            int lo = std::numeric_limits<int>::max();
+            int hi = std::numeric_limits<int>::min();
+            for (int i = 0; i < 4; i++) {
+                if (values[i] < lo) lo = values[i];
+                if (values[i] > hi) hi = values[i];
+            }
+            if (lo != -3 || hi != 12) return 1;
+            double eps = std::numeric_limits<double>::epsilon();
+            return 1.0 + eps != 1.0 ? 0 : 2;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_function_template.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_numeric_limits.cpp").expect("E2E test failed");
 
     assert_eq!(
         exit_code, 0,
-        "function template instantiation should work correctly"
+        "numeric_limits should map to the primitive's MIN/MAX/EPSILON"
     );
 }
 
-/// Test function template with pointer parameters.
-/// Verifies that templates like swap(T* a, T* b) extract T correctly (not T*).
 #[test]
-fn test_e2e_function_template_pointer_params() {
+fn test_e2e_distance_and_advance() {
     let source = r#"
-        template<typename T>
-        void swap(T* a, T* b) {
-            T tmp = *a;
-            *a = *b;
-            *b = tmp;
-        }
+        #include <iterator>
+        #include <vector>
 
         int main() {
-            // Test 1: swap two integers via pointers
-            int x = 100, y = 200;
-            swap(&x, &y);
-            if (x != 200 || y != 100) return 1;
-
-            // Test 2: swap again to verify it works both ways
-            swap(&x, &y);
-            if (x != 100 || y != 200) return 2;
+            std::vector<int> v;
+            v.push_back(1);
+            v.push_back(2);
+            v.push_back(3);
+            if (std::distance(v.begin(), v.end()) != 3) return 1;
 
-            return 0;  // All tests passed
+            int a[] = {10, 20, 30, 40};
+            int* p = a;
+            std::advance(p, 2);
+            return *p == 30 && std::distance(a, p) == 2 ? 0 : 2;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_function_template_ptr.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_distance_advance.cpp").expect("E2E test failed");
 
     assert_eq!(
         exit_code, 0,
-        "function template with pointer parameters should work correctly"
+        "std::distance and std::advance should match C++"
     );
 }
 
-/// Test function template with reference parameters.
-/// Verifies that templates like swap(T& a, T& b) extract T correctly.
 #[test]
-fn test_e2e_function_template_reference_params() {
+fn test_e2e_string_returning_call_temporaries() {
     let source = r#"
-        template<typename T>
-        void swap_ref(T& a, T& b) {
-            T tmp = a;
-            a = b;
-            b = tmp;
-        }
+        #include <string>
 
-        int main() {
-            // Test 1: swap two integers via references
-            int x = 50, y = 150;
-            swap_ref(x, y);
-            if (x != 150 || y != 50) return 1;
+        std::string getName(int id) {
+            return id == 1 ? std::string("alice") : std::string("bob");
+        }
 
-            // Test 2: swap again to verify
-            swap_ref(x, y);
-            if (x != 50 || y != 150) return 2;
+        int length(const std::string& s) {
+            return (int)s.size();
+        }
 
-            return 0;  // All tests passed
+        int main() {
+            int total = 0;
+            for (int i = 0; i < 1000; i++) {
+                total += length(getName(i % 2));
+            }
+            if (total != 4000) return 1;
+            size_t n = getName(1).size();
+            const std::string& kept = getName(2);
+            return n == 5 && kept == "bob" ? 0 : 2;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_function_template_ref.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_string_temporaries.cpp").expect("E2E test failed");
 
     assert_eq!(
         exit_code, 0,
-        "function template with reference parameters should work correctly"
+        "std::string temporaries returned from calls should be usable and freed"
     );
 }
 
-/// Test function template with multiple type parameters.
-/// Verifies that templates like pair<T, U> work with different instantiations.
 #[test]
-fn test_e2e_function_template_multiple_params() {
+fn test_e2e_vector_count_value_ctor() {
     let source = r#"
-        template<typename T, typename U>
-        T first_of(T a, U b) {
-            return a;
-        }
+        #include <vector>
 
-        template<typename T, typename U>
-        U second_of(T a, U b) {
-            return b;
+        int main() {
+            std::vector<int> v(4, 9);
+            if (v.size() != 4) return 1;
+            int sum = 0;
+            for (int x : v) sum += x;
+            if (sum != 36) return 2;
+            int src[] = {1, 2, 3};
+            std::vector<int> w(src, src + 3);
+            return w.size() == 3 && w[2] == 3 ? 0 : 3;
         }
+    "#;
 
-        int main() {
-            // Test 1: first_of with int, float
-            int a = first_of(42, 3.14f);
-            if (a != 42) return 1;
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_vector_count_value.cpp").expect("E2E test failed");
 
-            // Test 2: second_of with int, float (returns float as int)
-            float b = second_of(10, 2.5f);
-            if (b < 2.4f || b > 2.6f) return 2;
+    assert_eq!(
+        exit_code, 0,
+        "std::vector(count, value) and (first, last) should fill the vector"
+    );
+}
 
-            // Test 3: first_of with different types
-            int c = first_of(100, 'X');
-            if (c != 100) return 3;
+#[test]
+fn test_e2e_wide_and_unicode_chars() {
+    let source = r#"
+        int wide_len(const wchar_t* s) {
+            int n = 0;
+            while (s[n] != L'\0') n++;
+            return n;
+        }
 
-            return 0;  // All tests passed
+        int main() {
+            if (wide_len(L"hello") != 5) return 1;
+            const char16_t* u = u"ab";
+            char32_t c = U'z';
+            if (u[1] != u'b' || c != 122) return 2;
+            return sizeof(char16_t) == 2 && sizeof(char32_t) == 4 ? 0 : 3;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_function_template_multi.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_wide_chars.cpp").expect("E2E test failed");
 
     assert_eq!(
         exit_code, 0,
-        "function template with multiple type parameters should work correctly"
+        "wide and unicode character types should keep their widths"
     );
 }
 
-/// Test std_string stub operations directly in generated Rust code.
-/// This verifies the std_string stub in the preamble works correctly.
-/// Note: This test compiles hand-written Rust that uses the stub, rather than
-/// transpiling C++ std::string usage, because full std::string transpilation
-/// requires complete libc++ support (which is still in progress).
 #[test]
-fn test_e2e_std_string_stub() {
+fn test_e2e_int128_arithmetic() {
+    let source = r#"
+        unsigned __int128 mul_full(unsigned long long a, unsigned long long b) {
+            return (unsigned __int128)a * b;
+        }
+
+        int main() {
+            unsigned __int128 big = mul_full(18446744073709551615ULL, 4);
+            if ((unsigned long long)(big >> 64) != 3) return 1;
+            __int128 values[2] = {-5, 7};
+            __int128* p = values;
+            return p[0] + p[1] == 2 ? 0 : 2;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_int128.cpp").expect("E2E test failed");
+
+    assert_eq!(exit_code, 0, "__int128 values should keep all 128 bits");
+}
+
+#[test]
+fn test_e2e_throwing_destructor_terminates() {
+    let source = r#"
+        struct Loose {
+            ~Loose() noexcept(false) { throw 1; }
+        };
+
+        struct Guard {
+            ~Guard() { throw 2; }
+        };
+
+        int main() {
+            try {
+                Loose l;
+            } catch (int e) {
+                if (e != 1) return 1;
+            }
+            {
+                Guard g;
+            }
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_throwing_dtor.cpp").expect("E2E test failed");
+
+    // Aborted processes have no exit code; 0 or 1 would mean the exception escaped
+    assert_eq!(
+        exit_code, -1,
+        "an exception leaving an implicitly noexcept destructor should terminate"
+    );
+}
+
+#[test]
+fn test_e2e_string_data_mutable() {
+    let source = r#"
+        #include <string>
+
+        void upper_first(std::string& s) {
+            char* p = s.data();
+            if (p[0] >= 'a' && p[0] <= 'z') p[0] -= 32;
+        }
+
+        int main() {
+            std::string s = "hello";
+            upper_first(s);
+            s.data()[4] = '!';
+            const std::string& view = s;
+            const char* c = view.data();
+            return s == "Hell!" && c[0] == 'H' ? 0 : 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_string_data.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "writes through std::string::data() should change the string"
+    );
+}
+
+#[test]
+fn test_e2e_map_find_against_end() {
+    let source = r#"
+        #include <map>
+
+        int price(std::map<int, int>& prices, int id) {
+            auto it = prices.find(id);
+            if (it != prices.end()) {
+                return it->second;
+            }
+            return -1;
+        }
+
+        int main() {
+            std::map<int, int> prices = {{7, 70}, {3, 30}};
+            if (price(prices, 3) != 30) return 1;
+            if (price(prices, 5) != -1) return 2;
+            auto it = prices.find(7);
+            if (it == prices.end() || it->first != 7) return 3;
+            it->second = 71;
+            return prices[7] == 71 ? 0 : 4;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_map_find_end.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "map::find should return end() for missing keys and an iterator to the entry otherwise"
+    );
+}
+
+#[test]
+fn test_e2e_member_function_pointer_calls() {
+    let source = r#"
+        struct Shape {
+            int sides;
+            Shape() : sides(0) {}
+            virtual int area() { return 0; }
+            int scale(int k) { return sides * k; }
+        };
+
+        struct Square : Shape {
+            int side;
+            Square(int s) : side(s) { sides = 4; }
+            int area() override { return side * side; }
+        };
+
+        int main() {
+            Square sq(3);
+            Shape* p = &sq;
+            int (Shape::*measure)() = &Shape::area;
+            int (Shape::*times)(int) = &Shape::scale;
+            // Virtual methods dispatch on the dynamic type
+            if ((sq.*measure)() != 9) return 1;
+            if ((p->*measure)() != 9) return 2;
+            if ((p->*times)(5) != 20) return 3;
+            Shape plain;
+            return (plain.*measure)() == 0 ? 0 : 4;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_member_function_pointer.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Calls through pointers to member functions should reach the right method"
+    );
+}
+
+#[test]
+fn test_e2e_printf_snprintf() {
+    let source = r#"
+        #include <cstdio>
+
+        int main() {
+            char buf[8];
+            int n = snprintf(buf, sizeof(buf), "%d:%s", 12345, "abcdef");
+            if (n != 12) return 1;
+            if (buf[6] != 'a' || buf[7] != 0) return 2;
+            printf("%s|%5.2f|%x|%c|%%\n", "out", 3.14159, 255, 'z');
+            return 0;
+        }
+    "#;
+
+    let (exit_code, stdout, _stderr) =
+        transpile_compile_run(source, "e2e_printf_snprintf.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "snprintf should truncate and report the full length"
+    );
+    assert_eq!(stdout, "out| 3.14|ff|z|%\n");
+}
+
+#[test]
+fn test_e2e_printf_exponent_general_and_star_width() {
+    let source = r#"
+        #include <cstdio>
+
+        int main() {
+            printf("%e %g %*d\n", 1234.5, 0.0001, 5, 42);
+            printf("%.2e|%g|%-*d|%.*f\n", 0.000123, 1e20, 4, 7, 1, 2.25);
+            printf("%#x|% d|%hhd|%p\n", 255, 7, 300, (void*)0);
+            return 0;
+        }
+    "#;
+
+    let (exit_code, stdout, _stderr) =
+        transpile_compile_run(source, "e2e_printf_exponent.cpp").expect("E2E test failed");
+
+    assert_eq!(exit_code, 0, "printf should run to completion");
+    assert_eq!(
+        stdout,
+        "1.234500e+03 0.0001    42\n1.23e-04|1e+20|7   |2.2\n0xff| 7|44|(nil)\n"
+    );
+}
+
+#[test]
+fn test_e2e_function_pointer_typedef() {
+    let source = r#"
+        typedef int (*Handler)(int);
+
+        int twice(int x) { return x * 2; }
+        int negate(int x) { return -x; }
+
+        int call_deref(Handler h, int x) { return (*h)(x); }
+
+        int main() {
+            Handler h = nullptr;
+            h = twice;
+            if (h(21) != 42) return 1;
+
+            Handler table[2] = {twice, negate};
+            if (table[1](5) != -5) return 2;
+            if (call_deref(negate, 7) != -7) return 3;
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_function_pointer_typedef.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Calls through a function pointer typedef should dispatch to the assigned function"
+    );
+}
+
+#[test]
+fn test_e2e_setjmp_longjmp() {
+    let source = r#"
+        #include <csetjmp>
+
+        jmp_buf env;
+        int depth_reached = 0;
+
+        void descend(int depth) {
+            depth_reached = depth;
+            if (depth == 3) longjmp(env, 42);
+            descend(depth + 1);
+        }
+
+        int main() {
+            int landed = 0;
+            if (setjmp(env) == 0) {
+                descend(0);
+                landed = -1;
+            } else {
+                landed = 1;
+            }
+            if (landed != 1) return 2;
+            if (depth_reached != 3) return 3;
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_setjmp_longjmp.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "longjmp should unwind out of the nested calls back to setjmp"
+    );
+}
+
+#[test]
+fn test_e2e_setjmp_branch_returns_and_breaks() {
+    let source = r#"
+        #include <csetjmp>
+
+        jmp_buf env;
+
+        void fail(int code) { longjmp(env, code); }
+
+        int attempt(int code) {
+            if (setjmp(env) != 0) {
+                return code * 10;
+            } else {
+                fail(code);
+            }
+            return -1;
+        }
+
+        int main() {
+            if (attempt(4) != 40) return 1;
+            int rounds = 0;
+            while (true) {
+                rounds++;
+                if (setjmp(env) == 0) {
+                    fail(1);
+                } else {
+                    break;
+                }
+            }
+            if (rounds != 1) return 2;
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_setjmp_branch_exits.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "return and break in a setjmp branch should leave the function and the loop"
+    );
+}
+
+#[test]
+fn test_e2e_aligned_alloc_and_free() {
+    let source = r#"
+        #include <cstdlib>
+        #include <cstdint>
+
+        int main() {
+            int* p = (int*)aligned_alloc(64, 256);
+            if (p == nullptr) return 1;
+            if ((uintptr_t)p % 64 != 0) return 2;
+            for (int i = 0; i < 64; i++) p[i] = i;
+            if (p[63] != 63) return 3;
+            free(p);
+
+            void* q = nullptr;
+            if (posix_memalign(&q, 128, 64) != 0) return 4;
+            if ((uintptr_t)q % 128 != 0) return 5;
+            free(q);
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_aligned_alloc_free.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "aligned blocks should honor their alignment and be released by a one-argument free"
+    );
+}
+
+#[test]
+fn test_e2e_vector_of_unique_ptr() {
+    let source = r#"
+        #include <memory>
+        #include <vector>
+
+        int main() {
+            std::vector<std::unique_ptr<int>> ptrs;
+            for (int i = 1; i <= 10; i++) {
+                ptrs.push_back(std::make_unique<int>(i * i));
+            }
+            if (ptrs.size() != 10) return 1;
+            if (*ptrs[3] != 16) return 2;
+            int sum = 0;
+            for (int i = 0; i < 10; i++) {
+                sum += *ptrs[i];
+            }
+            return sum == 385 ? 0 : 3;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_vector_of_unique_ptr.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "A vector of unique_ptr should move elements in and keep them through reallocation"
+    );
+}
+
+#[test]
+fn test_e2e_vector_clear_and_refill() {
+    let source = r#"
+        #include <vector>
+
+        int main() {
+            std::vector<int> xs;
+            unsigned long cap = 0;
+            for (int round = 0; round < 100; round++) {
+                xs.clear();
+                for (int i = 0; i < 10; i++) {
+                    xs.push_back(round + i);
+                }
+                if (round == 0) cap = xs.capacity();
+                if (xs.capacity() != cap) return 1;
+            }
+            if (xs.size() != 10) return 2;
+            return xs[9] == 108 ? 0 : 3;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_vector_clear_and_refill.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "clear() should keep the vector's capacity so refilling does not reallocate"
+    );
+}
+
+#[test]
+fn test_e2e_vector_assign() {
+    let source = r#"
+        #include <vector>
+
+        int main() {
+            std::vector<int> v;
+            v.push_back(1);
+            v.assign(3, 7);
+            if (v.size() != 3) return 1;
+            if (v[0] != 7 || v[2] != 7) return 2;
+            int src[] = {4, 5, 6, 8};
+            v.assign(src, src + 4);
+            if (v.size() != 4) return 3;
+            return (v[0] == 4 && v[3] == 8) ? 0 : 4;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_vector_assign.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "vector::assign should replace the contents with copies of a value or a range"
+    );
+}
+
+#[test]
+fn test_e2e_memset_memcpy_container_data() {
+    let source = r#"
+        #include <array>
+        #include <cstring>
+        #include <vector>
+
+        int main() {
+            std::vector<int> v;
+            for (int i = 1; i <= 4; i++) v.push_back(i);
+            memset(v.data(), 0, v.size() * sizeof(int));
+            for (int i = 0; i < 4; i++) {
+                if (v[i] != 0) return 1;
+            }
+            std::array<int, 3> src = {7, 8, 9};
+            memcpy(v.data(), src.data(), src.size() * sizeof(int));
+            return (v[0] == 7 && v[2] == 9 && v[3] == 0) ? 0 : 2;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_memset_container_data.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "memset/memcpy on data() should write the container's elements"
+    );
+}
+
+#[test]
+fn test_e2e_range_for_forwarding_reference() {
+    let source = r#"
+        #include <vector>
+
+        template <typename Range>
+        void double_all(Range& range) {
+            for (auto&& x : range) {
+                x *= 2;
+            }
+        }
+
+        int sum(const std::vector<int>& values) {
+            int total = 0;
+            for (auto&& v : values) {
+                total += v;
+            }
+            return total;
+        }
+
+        int main() {
+            std::vector<int> values;
+            for (int i = 1; i <= 4; i++) values.push_back(i);
+            double_all(values);
+            if (values[0] != 2 || values[3] != 8) return 1;
+            return sum(values) == 20 ? 0 : 2;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_range_for_forwarding_ref.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "auto&& range-for should bind to (and mutate) the vector's elements"
+    );
+}
+
+#[test]
+fn test_e2e_string_compare_with_literal() {
+    let source = r#"
+        #include <string>
+
+        int check(const std::string& name) {
+            if (name == "admin") return 1;
+            if ("guest" == name) return 2;
+            return 0;
+        }
+
+        int main() {
+            std::string admin = "admin";
+            std::string guest = "guest";
+            std::string bob = "bob";
+            if (check(admin) != 1 || check(guest) != 2 || check(bob) != 0) return 1;
+            if (admin != "admin") return 2;
+            if (!(bob < "carol") || !("alice" < bob)) return 3;
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_string_compare_literal.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "std::string should compare against string literals on either side"
+    );
+}
+
+#[test]
+fn test_e2e_reinterpret_cast_type_punning() {
+    let source = r#"
+        int main() {
+            int bits = 0x3f800000;
+            float f = *reinterpret_cast<float*>(&bits);
+            if (f != 1.0f) return 1;
+            // A double read out of a byte buffer at an odd offset
+            double d = 2.5;
+            char buf[16] = {0};
+            unsigned char* src = reinterpret_cast<unsigned char*>(&d);
+            for (int i = 0; i < 8; i++) buf[i + 1] = src[i];
+            double back = *reinterpret_cast<double*>(buf + 1);
+            return back == 2.5 ? 0 : 2;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_reinterpret_cast_punning.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Reading through a reinterpret_cast pointer should yield the punned value"
+    );
+}
+
+/// E2E test: nullptr handling
+#[test]
+fn test_e2e_nullptr() {
+    let source = r#"
+        int* get_null() {
+            return nullptr;
+        }
+
+        int main() {
+            int* p = nullptr;
+            if (p == nullptr) {
+                return 0;
+            }
+            return 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_nullptr.cpp").expect("E2E test failed");
+
+    assert_eq!(exit_code, 0, "nullptr should be handled correctly");
+}
+
+/// E2E test: C++ casts (static_cast, reinterpret_cast)
+#[test]
+fn test_e2e_casts() {
+    let source = r#"
+        int test_static_cast(double d) {
+            return static_cast<int>(d);
+        }
+
+        int main() {
+            double d = 3.7;
+            int i = static_cast<int>(d);
+            if (i == 3) {
+                return 0;
+            }
+            return 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_casts.cpp").expect("E2E test failed");
+
+    assert_eq!(exit_code, 0, "static_cast<int>(3.7) should equal 3");
+}
+
+/// E2E test: scalar static_cast, pointer reinterpret_cast and const_cast
+#[test]
+fn test_e2e_casts_pointers_and_constness() {
+    let source = r#"
+        int main() {
+            int a = 7;
+            double d = static_cast<double>(a) / 2;
+            if (d != 3.5) return 1;
+            if ((int)d != 3) return 2;
+
+            unsigned int bits = 0x01020304;
+            unsigned char* bytes = reinterpret_cast<unsigned char*>(&bits);
+            if (bytes[0] != 0x04) return 3;
+
+            const int* cp = &a;
+            int* p = const_cast<int*>(cp);
+            *p = 9;
+            if (a != 9) return 4;
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_casts_pointers.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "explicit casts should convert values and pointers"
+    );
+}
+
+/// E2E test: main(int, char**) receives the process arguments and its return
+/// value becomes the exit code
+#[test]
+fn test_e2e_main_with_argc_argv() {
+    let source = r#"
+        int main(int argc, char** argv) {
+            if (argc != 1) return 100;
+            if (argv[0] == nullptr) return 101;
+            if (argv[1] != nullptr) return 102;
+            return 42;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_main_argc_argv.cpp").expect("E2E test failed");
+
+    assert_eq!(exit_code, 42, "main's return value should be the exit code");
+}
+
+/// E2E test: new/delete
+#[test]
+fn test_e2e_new_delete() {
+    let source = r#"
+        int main() {
+            int* p = new int(42);
+            int v = *p;
+            delete p;
+            if (v == 42) {
+                return 0;
+            }
+            return 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_new_delete.cpp").expect("E2E test failed");
+
+    assert_eq!(exit_code, 0, "new int(42) should create value 42");
+}
+
+/// E2E test: new[]/delete[] (array allocation)
+#[test]
+fn test_e2e_array_new_delete() {
+    let source = r#"
+        int main() {
+            int* arr = new int[5];
+            arr[0] = 10;
+            arr[1] = 20;
+            int sum = arr[0] + arr[1];
+            delete[] arr;
+            if (sum == 30) {
+                return 0;
+            }
+            return 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_array_new.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "new int[5] should create array that can be indexed"
+    );
+}
+
+/// E2E test: Single inheritance
+#[test]
+fn test_e2e_inheritance() {
+    let source = r#"
+        struct Animal {
+            int legs;
+        };
+
+        struct Dog : public Animal {
+            int tail;
+        };
+
+        int main() {
+            Dog d;
+            d.legs = 4;  // Inherited from Animal
+            d.tail = 1;
+            if (d.legs == 4 && d.tail == 1) {
+                return 0;
+            }
+            return 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_inheritance.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Inheritance should embed base struct as __base field"
+    );
+}
+
+/// E2E test: Destructor → Drop trait
+#[test]
+fn test_e2e_destructor() {
+    let source = r#"
+        struct Resource {
+            int value;
+
+            Resource() {
+                value = 100;
+            }
+
+            ~Resource() {
+                // Destructor body - this code runs when Drop::drop is called
+                value = 0;  // Reset value on destruction
+            }
+
+            int get() {
+                return value;
+            }
+        };
+
+        int main() {
+            Resource r;
+            int v = r.get();
+            if (v == 100) {
+                return 0;
+            }
+            return 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_destructor.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Struct with destructor should compile and run"
+    );
+}
+
+#[test]
+fn test_e2e_copy_constructor() {
+    let source = r#"
+        struct Point {
+            int x;
+            int y;
+
+            Point() {
+                x = 0;
+                y = 0;
+            }
+
+            Point(int px, int py) {
+                x = px;
+                y = py;
+            }
+
+            // Copy constructor
+            Point(const Point& other) {
+                x = other.x;
+                y = other.y;
+            }
+
+            int sum() {
+                return x + y;
+            }
+        };
+
+        int main() {
+            Point a(10, 20);
+            Point b = a;  // Uses copy constructor
+            // Verify both a and b have the same values
+            if (a.sum() == 30 && b.sum() == 30) {
+                return 0;
+            }
+            return 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_copy_ctor.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Struct with copy constructor should compile and run"
+    );
+}
+
+#[test]
+fn test_e2e_exception_handling() {
+    let source = r#"
+        int divide(int a, int b) {
+            if (b == 0) {
+                throw "Division by zero";
+            }
+            return a / b;
+        }
+
+        int safe_divide(int a, int b) {
+            try {
+                return divide(a, b);
+            } catch (...) {
+                return -1;
+            }
+        }
+
+        int main() {
+            // Test normal division
+            int r1 = safe_divide(10, 2);
+            if (r1 != 5) return 1;
+
+            // Test division by zero (should catch and return -1)
+            int r2 = safe_divide(10, 0);
+            if (r2 != -1) return 2;
+
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_exception.cpp").expect("E2E test failed");
+
+    assert_eq!(exit_code, 0, "Exception handling should compile and run");
+}
+
+/// Test catch by value (slices to the handler type) versus catch by reference
+/// (keeps the thrown object's dynamic type for virtual calls).
+#[test]
+fn test_e2e_exception_catch_by_value_and_reference() {
+    let source = r#"
+        struct BaseError {
+            int code;
+            BaseError(int c) : code(c) {}
+            virtual int kind() { return 1; }
+        };
+
+        struct DerivedError : BaseError {
+            DerivedError(int c) : BaseError(c) {}
+            int kind() override { return 2; }
+        };
+
+        int catch_by_ref(int c) {
+            try {
+                throw DerivedError(c);
+            } catch (BaseError& e) {
+                return e.kind() * 100 + e.code;
+            }
+            return 0;
+        }
+
+        int catch_by_value(int c) {
+            try {
+                throw DerivedError(c);
+            } catch (BaseError e) {
+                return e.kind() * 100 + e.code;
+            }
+            return 0;
+        }
+
+        int first_match(int v) {
+            try {
+                throw v;
+            } catch (BaseError& e) {
+                return -1;
+            } catch (int n) {
+                return n;
+            }
+            return 0;
+        }
+
+        int main() {
+            // Reference handlers dispatch to the derived override
+            if (catch_by_ref(7) != 207) return 1;
+            // Value handlers copy only the base part of the object
+            if (catch_by_value(7) != 107) return 2;
+            // Handlers are matched by type, in order
+            if (first_match(42) != 42) return 3;
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_exception_catch_kinds.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "catch by value should slice and catch by reference should dispatch virtually"
+    );
+}
+
+#[test]
+fn test_e2e_namespaces() {
+    let source = r#"
+        namespace math {
+            int add(int a, int b) {
+                return a + b;
+            }
+
+            namespace utils {
+                int multiply(int a, int b) {
+                    return a * b;
+                }
+            }
+        }
+
+        int main() {
+            int r1 = math::add(2, 3);
+            int r2 = math::utils::multiply(4, 5);
+            if (r1 == 5 && r2 == 20) {
+                return 0;
+            }
+            return 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_namespace.cpp").expect("E2E test failed");
+
+    assert_eq!(exit_code, 0, "Namespace functions should compile and run");
+}
+
+/// Test virtual method override resolution (static dispatch).
+#[test]
+fn test_e2e_virtual_override() {
+    let source = r#"
+        class Animal {
+        public:
+            virtual int speak() {
+                return 1;
+            }
+            int eat() {
+                return 10;
+            }
+        };
+
+        class Dog : public Animal {
+        public:
+            int speak() override {
+                return 2;
+            }
+        };
+
+        int main() {
+            Dog d;
+            int a = d.speak();      // 2 (overridden)
+            int b = d.eat();        // 10 (inherited)
+            if (a == 2 && b == 10) {
+                return 0;
+            }
+            return 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_virtual_override.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Virtual method override should work correctly"
+    );
+}
+
+/// Test base class constructor delegation.
+#[test]
+fn test_e2e_base_constructor() {
+    let source = r#"
+        class Base {
+        protected:
+            int x;
+            int y;
+        public:
+            Base(int a, int b) : x(a), y(b) {}
+        };
+
+        class Derived : public Base {
+            int z;
+        public:
+            Derived(int a, int b, int c) : Base(a, b), z(c) {}
+            int sum() { return x + y + z; }
+        };
+
+        int main() {
+            Derived d(10, 20, 30);
+            if (d.sum() == 60) {
+                return 0;
+            }
+            return 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_base_constructor.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Base class constructor delegation should work"
+    );
+}
+
+/// Test operator overloading.
+#[test]
+fn test_e2e_operator_overloading() {
+    let source = r#"
+        class Point {
+            int x;
+            int y;
+        public:
+            Point(int a, int b) : x(a), y(b) {}
+            Point operator+(const Point& other) const {
+                return Point(x + other.x, y + other.y);
+            }
+            bool operator==(const Point& other) const {
+                return x == other.x && y == other.y;
+            }
+            int getX() const { return x; }
+            int getY() const { return y; }
+        };
+
+        int main() {
+            Point a(1, 2);
+            Point b(3, 4);
+            Point c = a + b;
+            if (c.getX() == 4 && c.getY() == 6) {
+                return 0;
+            }
+            return 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_operator_overloading.cpp").expect("E2E test failed");
+
+    assert_eq!(exit_code, 0, "Operator overloading should work correctly");
+}
+
+/// Test dynamic dispatch (virtual method polymorphism).
+#[test]
+fn test_e2e_dynamic_dispatch() {
+    let source = r#"
+        class Animal {
+        public:
+            virtual int speak() { return 1; }
+        };
+
+        class Dog : public Animal {
+        public:
+            int speak() override { return 2; }
+        };
+
+        class Cat : public Animal {
+        public:
+            int speak() override { return 3; }
+        };
+
+        int callSpeak(Animal* a) {
+            return a->speak();
+        }
+
+        int main() {
+            Dog d;
+            Cat c;
+            // Dynamic dispatch: should call Dog::speak() and Cat::speak()
+            int result = callSpeak(&d) + callSpeak(&c);
+            if (result == 5) {
+                return 0;
+            }
+            return 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_dynamic_dispatch.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Dynamic dispatch should correctly call derived class methods"
+    );
+}
+
+/// Test deep inheritance hierarchy (A → B → C → D) with virtual methods.
+/// Verifies vtable dispatch works through multiple inheritance levels.
+#[test]
+fn test_e2e_deep_inheritance() {
+    let source = r#"
+        // Root class with virtual method
+        class Base {
+        public:
+            virtual int level() { return 0; }
+        };
+
+        // Level 1 - overrides Base::level
+        class Level1 : public Base {
+        public:
+            int level() override { return 1; }
+        };
+
+        // Level 2 - overrides Level1::level
+        class Level2 : public Level1 {
+        public:
+            int level() override { return 2; }
+        };
+
+        // Level 3 - overrides Level2::level
+        class Level3 : public Level2 {
+        public:
+            int level() override { return 3; }
+        };
+
+        // Level 4 - does NOT override, inherits Level3::level
+        class Level4 : public Level3 {
+        public:
+            // No override - should still return 3
+        };
+
+        int getLevel(Base* b) {
+            return b->level();
+        }
+
+        int main() {
+            Base b;
+            Level1 l1;
+            Level2 l2;
+            Level3 l3;
+            Level4 l4;
+
+            // Test virtual dispatch at each level
+            int sum = getLevel(&b) + getLevel(&l1) + getLevel(&l2) + getLevel(&l3) + getLevel(&l4);
+            // Expected: 0 + 1 + 2 + 3 + 3 = 9
+            if (sum == 9) {
+                return 0;
+            }
+            return 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_deep_inheritance.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Deep inheritance virtual dispatch should work correctly"
+    );
+}
+
+/// Test that RTTI type IDs are generated correctly for vtables.
+/// Verifies the RTTI infrastructure exists without testing dynamic_cast directly.
+#[test]
+fn test_e2e_vtable_rtti() {
+    let source = r#"
+        class Animal {
+        public:
+            virtual int id() { return 1; }
+            virtual ~Animal() {}
+        };
+
+        class Dog : public Animal {
+        public:
+            int id() override { return 2; }
+        };
+
+        class Cat : public Animal {
+        public:
+            int id() override { return 3; }
+        };
+
+        int main() {
+            Animal a;
+            Dog d;
+            Cat c;
+
+            // Test that virtual dispatch works correctly
+            Animal* pa = &a;
+            Animal* pd = &d;
+            Animal* pc = &c;
+
+            int sum = pa->id() + pd->id() + pc->id();
+            // Expected: 1 + 2 + 3 = 6
+            if (sum == 6) {
+                return 0;  // Success
+            }
+            return 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_vtable_rtti.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "RTTI type IDs should be generated for vtables"
+    );
+}
+
+/// Test dynamic_cast with RTTI.
+/// Verifies that dynamic_cast correctly returns the pointer on success
+/// and nullptr on failure.
+#[test]
+fn test_e2e_dynamic_cast() {
+    let source = r#"
+        class Base {
+        public:
+            virtual int id() { return 1; }
+            virtual ~Base() {}
+        };
+
+        class Derived : public Base {
+        public:
+            int id() override { return 2; }
+        };
+
+        int main() {
+            Derived d;
+            Base b;
+
+            Base* pd = &d;  // Points to Derived
+            Base* pb = &b;  // Points to Base
+
+            // Test 1: dynamic_cast should succeed when actual type matches
+            Derived* d1 = dynamic_cast<Derived*>(pd);
+            if (d1 == nullptr) return 1;  // Should not be null
+
+            // Test 2: dynamic_cast should fail when actual type doesn't match
+            Derived* d2 = dynamic_cast<Derived*>(pb);
+            if (d2 != nullptr) return 3;  // Should be null
+
+            // Test 3: dynamic_cast on nullptr should return nullptr
+            Base* null_ptr = nullptr;
+            Derived* d3 = dynamic_cast<Derived*>(null_ptr);
+            if (d3 != nullptr) return 4;  // Should be null
+
+            return 0;  // All tests passed
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_dynamic_cast.cpp").expect("E2E test failed");
+
+    assert_eq!(exit_code, 0, "dynamic_cast should work correctly with RTTI");
+}
+
+/// Test function template instantiation.
+/// Verifies that function templates are correctly instantiated with concrete types.
+#[test]
+fn test_e2e_function_template() {
+    let source = r#"
+        template<typename T>
+        T add(T a, T b) {
+            return a + b;
+        }
+
+        template<typename T>
+        T identity(T x) {
+            return x;
+        }
+
+        int main() {
+            // Test 1: int instantiation of add
+            int sum = add(3, 4);
+            if (sum != 7) return 1;
+
+            // Test 2: identity function template
+            int x = identity(42);
+            if (x != 42) return 2;
+
+            return 0;  // All tests passed
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_function_template.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "function template instantiation should work correctly"
+    );
+}
+
+/// Test function template with pointer parameters.
+/// Verifies that templates like swap(T* a, T* b) extract T correctly (not T*).
+#[test]
+fn test_e2e_function_template_pointer_params() {
+    let source = r#"
+        template<typename T>
+        void swap(T* a, T* b) {
+            T tmp = *a;
+            *a = *b;
+            *b = tmp;
+        }
+
+        int main() {
+            // Test 1: swap two integers via pointers
+            int x = 100, y = 200;
+            swap(&x, &y);
+            if (x != 200 || y != 100) return 1;
+
+            // Test 2: swap again to verify it works both ways
+            swap(&x, &y);
+            if (x != 100 || y != 200) return 2;
+
+            return 0;  // All tests passed
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_function_template_ptr.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "function template with pointer parameters should work correctly"
+    );
+}
+
+/// Test function template with reference parameters.
+/// Verifies that templates like swap(T& a, T& b) extract T correctly.
+#[test]
+fn test_e2e_function_template_reference_params() {
+    let source = r#"
+        template<typename T>
+        void swap_ref(T& a, T& b) {
+            T tmp = a;
+            a = b;
+            b = tmp;
+        }
+
+        int main() {
+            // Test 1: swap two integers via references
+            int x = 50, y = 150;
+            swap_ref(x, y);
+            if (x != 150 || y != 50) return 1;
+
+            // Test 2: swap again to verify
+            swap_ref(x, y);
+            if (x != 50 || y != 150) return 2;
+
+            return 0;  // All tests passed
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_function_template_ref.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "function template with reference parameters should work correctly"
+    );
+}
+
+/// Test function template with multiple type parameters.
+/// Verifies that templates like pair<T, U> work with different instantiations.
+#[test]
+fn test_e2e_function_template_multiple_params() {
+    let source = r#"
+        template<typename T, typename U>
+        T first_of(T a, U b) {
+            return a;
+        }
+
+        template<typename T, typename U>
+        U second_of(T a, U b) {
+            return b;
+        }
+
+        int main() {
+            // Test 1: first_of with int, float
+            int a = first_of(42, 3.14f);
+            if (a != 42) return 1;
+
+            // Test 2: second_of with int, float (returns float as int)
+            float b = second_of(10, 2.5f);
+            if (b < 2.4f || b > 2.6f) return 2;
+
+            // Test 3: first_of with different types
+            int c = first_of(100, 'X');
+            if (c != 100) return 3;
+
+            return 0;  // All tests passed
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_function_template_multi.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "function template with multiple type parameters should work correctly"
+    );
+}
+
+/// E2E test: `if constexpr` in a class template member keeps only the branch
+/// selected for each instantiation.
+/// NOTE: This test is ignored because including <type_traits> header pulls in STL internals
+/// that generate invalid Rust code. The lowering itself is covered by the
+/// test_constexpr_if_in_template_member unit test in ast_codegen.
+#[test]
+#[ignore]
+fn test_e2e_constexpr_if_template_member() {
+    let source = r#"
+        #include <type_traits>
+
+        template<typename T>
+        struct Container {
+            T value;
+            int kind() {
+                if constexpr (std::is_same_v<T, int>) {
+                    return 1;
+                } else {
+                    return 2;
+                }
+            }
+        };
+
+        int main() {
+            Container<int> a;
+            a.value = 3;
+            Container<double> b;
+            b.value = 0.5;
+            if (a.kind() != 1) return 1;
+            if (b.kind() != 2) return 2;
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_constexpr_if_template.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "if constexpr should select the branch per instantiation"
+    );
+}
+
+/// Test std_string stub operations directly in generated Rust code.
+/// This verifies the std_string stub in the preamble works correctly.
+/// Note: This test compiles hand-written Rust that uses the stub, rather than
+/// transpiling C++ std::string usage, because full std::string transpilation
+/// requires complete libc++ support (which is still in progress).
+#[test]
+fn test_e2e_std_string_stub() {
     use std::fs;
     use std::process::Command;
 
@@ -2259,23 +4134,73 @@ fn test_e2e_multiple_inheritance() {
         };
 
         int main() {
-            Duck d;
-            d.setAltitude(100);
-            d.setDepth(5);
-
-            if (d.getAltitude() == 100 && d.getDepth() == 5 && d.age == 1) {
-                return 0;
-            }
-            return 1;
+            Duck d;
+            d.setAltitude(100);
+            d.setDepth(5);
+
+            if (d.getAltitude() == 100 && d.getDepth() == 5 && d.age == 1) {
+                return 0;
+            }
+            return 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_multiple_inheritance.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Multiple inheritance should work correctly with access to both base classes"
+    );
+}
+
+/// Test upcasting to a non-primary base followed by a virtual call.
+#[test]
+fn test_e2e_upcast_non_primary_base() {
+    let source = r#"
+        class Named {
+        public:
+            int id;
+            Named() : id(7) {}
+            virtual int name() { return 1; }
+        };
+
+        class Shape {
+        public:
+            int sides;
+            Shape() : sides(0) {}
+            virtual int area() { return 0; }
+        };
+
+        class Square : public Named, public Shape {
+        public:
+            int side;
+            Square() : side(4) { sides = 4; }
+            int name() override { return 2; }
+            int area() override { return side * side; }
+        };
+
+        int main() {
+            Square sq;
+            Shape* shape = static_cast<Shape*>(&sq);
+            Named* named = &sq;
+
+            // The Shape subobject is not at offset 0, so the pointer must be adjusted
+            if (shape->sides != 4) return 1;
+            // Virtual calls through the non-primary base reach Square's override
+            if (shape->area() != 16) return 2;
+            if (named->name() != 2) return 3;
+            if (named->id != 7) return 4;
+            return 0;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_multiple_inheritance.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_upcast_non_primary_base.cpp").expect("E2E test failed");
 
     assert_eq!(
         exit_code, 0,
-        "Multiple inheritance should work correctly with access to both base classes"
+        "Upcast to a non-primary base should adjust the pointer and dispatch virtually"
     );
 }
 
@@ -2306,6 +4231,37 @@ fn test_e2e_enum_class() {
     );
 }
 
+/// Test enums with an explicit underlying type, explicit values, and int conversions.
+#[test]
+fn test_e2e_enum_underlying_type_and_casts() {
+    let source = r#"
+        enum class Color : unsigned char { Red, Green = 5, Blue };
+        enum Mode { Off, On = 3 };
+
+        int main() {
+            Color c = static_cast<Color>(6);
+            if (c != Color::Blue) return 1;
+            if (static_cast<int>(c) != 6) return 2;
+            if (sizeof(Color) != 1) return 3;
+
+            // Unscoped enumerators are usable unqualified and convert to int
+            Mode m = On;
+            int n = m;
+            if (n != 3) return 4;
+            if (Off != 0) return 5;
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_enum_underlying_casts.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Enums should honor the underlying type and convert to/from integers"
+    );
+}
+
 /// Test static class members.
 #[test]
 fn test_e2e_static_members() {
@@ -2395,6 +4351,75 @@ fn test_e2e_lambda_captures() {
     assert_eq!(exit_code, 0, "Lambda captures should work correctly");
 }
 
+/// Test lambdas with mixed explicit captures stored in variables and called later.
+#[test]
+fn test_e2e_lambda_mixed_captures() {
+    let source = r#"
+        int main() {
+            int x = 10;
+            int total = 0;
+
+            // By-value capture is copied when the lambda is created
+            auto add_x = [x](int y) { return x + y; };
+            // Mixed: total by reference, x by value
+            auto accumulate = [&total, x](int y) { total += x + y; };
+            x = 100;
+
+            accumulate(add_x(1));  // total = 10 + 11 = 21
+            if (total != 21) return 1;
+
+            int scale = 2;
+            auto scaled = [=](int v) -> int { return v * scale; };
+            auto bump = [&](int v) { total += v; };
+            bump(scaled(4));  // total = 21 + 8 = 29
+
+            return total == 29 ? 0 : 2;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_lambda_mixed_captures.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Lambdas with mixed captures should work correctly"
+    );
+}
+
+/// Test lambdas capturing `auto`-declared variables, which take the deduced types.
+#[test]
+fn test_e2e_lambda_captures_auto_variables() {
+    let source = r#"
+        #include <vector>
+
+        int main() {
+            auto values = std::vector<int>{1, 2, 3, 4};
+            auto offset = 10;
+            auto base = &offset;
+
+            // The vector is copied into the closure; the outer one stays usable
+            auto sum = [values, offset]() {
+                int total = offset;
+                for (int v : values) total += v;
+                return total;
+            };
+            auto read = [base]() { return *base; };
+
+            if (sum() != 20) return 1;
+            if (values.size() != 4) return 2;
+            return read() == 10 ? 0 : 3;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_lambda_captures_auto.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Lambdas should capture auto variables with their deduced types"
+    );
+}
+
 /// Test generic lambdas (auto parameters).
 /// Note: In Rust, closures can only have one concrete type, so generic lambdas
 /// can only be used with one type instantiation.
@@ -2586,6 +4611,59 @@ fn test_e2e_switch() {
     );
 }
 
+/// Test E2E: Switch with break-terminated cases inside a loop, fallthrough bodies and default
+#[test]
+fn test_e2e_switch_break_and_fallthrough() {
+    let source = r#"
+        int classify(int x) {
+            int r = 0;
+            switch (x) {
+                case 1:
+                    r = 10;
+                    break;
+                case 2:
+                    r = r + 1;
+                case 3:
+                    r = r + 2;
+                    break;
+                default:
+                    r = -1;
+                    break;
+            }
+            return r;
+        }
+
+        int main() {
+            int total = 0;
+            for (int i = 0; i < 3; i = i + 1) {
+                // break inside the switch must not leave the loop
+                switch (i) {
+                    case 0:
+                        total = total + 1;
+                        break;
+                    default:
+                        total = total + 10;
+                        break;
+                }
+            }
+            if (total != 21) return 1;
+            if (classify(1) != 10) return 2;
+            if (classify(2) != 3) return 3;
+            if (classify(3) != 2) return 4;
+            if (classify(9) != -1) return 5;
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_switch_break.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Switch breaks should end the case, and fallthrough should run the next case"
+    );
+}
+
 /// Test E2E: Comma operator
 #[test]
 fn test_e2e_comma_operator() {
@@ -2787,6 +4865,39 @@ fn test_e2e_namespace_path_resolution() {
     );
 }
 
+/// Test calls through namespace aliases, which Clang resolves to the aliased namespace.
+#[test]
+fn test_e2e_namespace_alias() {
+    let source = r#"
+        namespace outer {
+            namespace inner {
+                int answer() { return 42; }
+            }
+        }
+
+        namespace client {
+            // Only visible inside client
+            namespace a = outer::inner;
+            int ask() { return a::answer(); }
+        }
+
+        namespace a = outer::inner;
+
+        int main() {
+            if (client::ask() != 42) return 1;
+            return a::answer() == 42 ? 0 : 2;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_namespace_alias.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Calls through a namespace alias should resolve to the aliased namespace"
+    );
+}
+
 /// Test function call operator (functor/callable object).
 #[test]
 fn test_e2e_functor() {
@@ -2953,6 +5064,67 @@ fn test_e2e_assignment_operators() {
     assert_eq!(exit_code, 0, "Assignment operators should work correctly");
 }
 
+/// Test compound assignment on scalars, references, pointers and mixed operand types.
+#[test]
+fn test_e2e_compound_assignment_scalars() {
+    let source = r#"
+        void bump(int& r, int by) {
+            r += by;
+            r <<= 1;
+        }
+
+        int main() {
+            int a = 10;
+            a += 5;
+            a -= 3;
+            a *= 2;
+            a /= 4;
+            a %= 4;
+            if (a != 2) return 1;
+
+            unsigned int x = 3;
+            x <<= 2;
+            x |= 1;
+            x ^= 4;
+            x >>= 1;
+            x &= 7;
+            if (x != 4) return 2;
+
+            int b = 1;
+            bump(b, 2);
+            if (b != 6) return 3;
+
+            int arr[4] = {1, 2, 3, 4};
+            int* p = arr;
+            p += 2;
+            if (*p != 3) return 4;
+            p -= 1;
+            if (*p != 2) return 5;
+
+            // Mixed operand types convert back to the left-hand type
+            char c = 'a';
+            c += a;
+            if (c != 'c') return 6;
+            double d = 1.5;
+            d *= a;
+            if (d != 3.0) return 7;
+            int n = 1;
+            n += d;
+            if (n != 4) return 8;
+
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_compound_assign.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Compound assignment operators should work correctly"
+    );
+}
+
 /// Test dereference operator * for smart pointer types.
 #[test]
 fn test_e2e_deref_operator() {
@@ -3093,6 +5265,36 @@ fn test_e2e_sizeof_alignof() {
     );
 }
 
+/// Test sizeof/alignof used directly in comparisons and arithmetic, where no
+/// implicit conversion lets Clang fold them.
+#[test]
+fn test_e2e_sizeof_in_expressions() {
+    let source = r#"
+        struct Point {
+            int x;
+            int y;
+        };
+
+        int main() {
+            if (sizeof(int) != 4) return 1;
+            if (sizeof(double) != 8) return 2;
+            if (alignof(long long) != 8) return 3;
+            if (sizeof(Point) != 8) return 4;
+            int values[6];
+            if (sizeof(values) / sizeof(values[0]) != 6) return 5;
+            Point p;
+            p.x = 1;
+            if (sizeof(p) != sizeof(Point)) return 6;
+            return 0;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_sizeof_in_expressions.cpp").expect("E2E test failed");
+
+    assert_eq!(exit_code, 0, "sizeof/alignof should match the C++ sizes");
+}
+
 /// Test string literals and implicit char-to-int casts.
 #[test]
 fn test_e2e_string_literals_and_char_casts() {
@@ -4254,9 +6456,195 @@ fn test_operator_new_delete_mapping() {
         &rust_code[..rust_code.len().min(3000)]
     );
 
-    println!("=== Operator new/delete mapping test ===");
-    println!("operator new -> fragile_malloc: OK");
-    println!("operator delete -> fragile_free: OK");
+    println!("=== Operator new/delete mapping test ===");
+    println!("operator new -> fragile_malloc: OK");
+    println!("operator delete -> fragile_free: OK");
+}
+
+/// Verify aligned_alloc / posix_memalign are mapped to fragile-runtime.
+#[test]
+fn test_aligned_alloc_mapping() {
+    let parser = ClangParser::new().expect("Failed to create parser");
+
+    let source = r#"
+        #include <cstdlib>
+
+        int main() {
+            void* a = aligned_alloc(64, 256);
+            void* b = nullptr;
+            int rc = posix_memalign(&b, 64, 100);
+            free(a);
+            free(b);
+            return rc;
+        }
+    "#;
+
+    let ast = parser
+        .parse_string(source, "aligned_alloc_test.cpp")
+        .expect("Failed to parse");
+    let rust_code = AstCodeGen::new().generate(&ast.translation_unit);
+
+    assert!(
+        rust_code.contains("fragile_runtime::fragile_aligned_alloc("),
+        "aligned_alloc should be mapped to fragile_runtime::fragile_aligned_alloc\nGenerated code:\n{}",
+        &rust_code[..rust_code.len().min(3000)]
+    );
+    assert!(
+        rust_code.contains("fragile_runtime::fragile_posix_memalign("),
+        "posix_memalign should be mapped to fragile_runtime::fragile_posix_memalign\nGenerated code:\n{}",
+        &rust_code[..rust_code.len().min(3000)]
+    );
+}
+
+#[test]
+fn test_atomic_compare_exchange_mapping() {
+    let parser = ClangParser::new().expect("Failed to create parser");
+
+    let source = r#"
+        int counter = 0;
+
+        bool bump(int expected) {
+            return __atomic_compare_exchange_n(&counter, &expected, expected + 1, false,
+                                               __ATOMIC_SEQ_CST, __ATOMIC_RELAXED);
+        }
+
+        int main() {
+            return bump(0) && !bump(0) ? 0 : 1;
+        }
+    "#;
+
+    let ast = parser
+        .parse_string(source, "atomic_cas_test.cpp")
+        .expect("Failed to parse");
+    let rust_code = AstCodeGen::new().generate(&ast.translation_unit);
+
+    assert!(
+        rust_code.contains("fragile_runtime::fragile_atomic_compare_exchange_i32("),
+        "__atomic_compare_exchange_n on int should be mapped to fragile_atomic_compare_exchange_i32\nGenerated code:\n{}",
+        &rust_code[..rust_code.len().min(3000)]
+    );
+}
+
+#[test]
+fn test_printf_mapping() {
+    let parser = ClangParser::new().expect("Failed to create parser");
+
+    let source = r#"
+        #include <cstdio>
+
+        int main() {
+            char buf[32];
+            int n = snprintf(buf, sizeof(buf), "%d-%s", 42, "x");
+            printf("%s %d\n", buf, n);
+            return 0;
+        }
+    "#;
+
+    let ast = parser
+        .parse_string(source, "printf_test.cpp")
+        .expect("Failed to parse");
+    let rust_code = AstCodeGen::new().generate(&ast.translation_unit);
+
+    assert!(
+        rust_code.contains("fragile_runtime::fragile_printf("),
+        "printf should be mapped to fragile_runtime::fragile_printf\nGenerated code:\n{}",
+        &rust_code[..rust_code.len().min(3000)]
+    );
+    assert!(
+        rust_code.contains("fragile_runtime::fragile_snprintf("),
+        "snprintf should be mapped to fragile_runtime::fragile_snprintf\nGenerated code:\n{}",
+        &rust_code[..rust_code.len().min(3000)]
+    );
+    assert!(
+        rust_code.contains("fragile_runtime::PrintfArg::from("),
+        "printf varargs should be packed as PrintfArg values\nGenerated code:\n{}",
+        &rust_code[..rust_code.len().min(3000)]
+    );
+}
+
+#[test]
+fn test_setjmp_longjmp_mapping() {
+    let parser = ClangParser::new().expect("Failed to create parser");
+
+    let source = r#"
+        #include <csetjmp>
+
+        static jmp_buf env;
+
+        void fail(int code) { longjmp(env, code); }
+
+        int main() {
+            int result = 0;
+            if (setjmp(env) == 0) {
+                fail(3);
+            } else {
+                result = 1;
+            }
+            return result;
+        }
+    "#;
+
+    let ast = parser
+        .parse_string(source, "setjmp_test.cpp")
+        .expect("Failed to parse");
+    let rust_code = AstCodeGen::new().generate(&ast.translation_unit);
+
+    assert!(
+        rust_code.contains("fragile_runtime::fragile_setjmp("),
+        "setjmp should be mapped to fragile_runtime::fragile_setjmp\nGenerated code:\n{}",
+        &rust_code[..rust_code.len().min(3000)]
+    );
+    assert!(
+        rust_code.contains("fragile_runtime::fragile_longjmp("),
+        "longjmp should be mapped to fragile_runtime::fragile_longjmp\nGenerated code:\n{}",
+        &rust_code[..rust_code.len().min(3000)]
+    );
+    assert!(
+        rust_code.contains("fragile_runtime::fragile_setjmp_catch("),
+        "the if statement calling setjmp should run in a jump region\nGenerated code:\n{}",
+        &rust_code[..rust_code.len().min(3000)]
+    );
+}
+
+#[test]
+fn test_pthread_key_mapping() {
+    let parser = ClangParser::new().expect("Failed to create parser");
+
+    let source = r#"
+        #include <pthread.h>
+
+        static pthread_key_t key;
+
+        void destroy(void* value) {}
+
+        int main() {
+            pthread_key_create(&key, destroy);
+            int local = 1;
+            pthread_setspecific(key, &local);
+            int ok = pthread_getspecific(key) == &local;
+            pthread_key_delete(key);
+            return ok ? 0 : 1;
+        }
+    "#;
+
+    let ast = parser
+        .parse_string(source, "pthread_key_test.cpp")
+        .expect("Failed to parse");
+    let rust_code = AstCodeGen::new().generate(&ast.translation_unit);
+
+    for name in [
+        "fragile_pthread_key_create(",
+        "fragile_pthread_setspecific(",
+        "fragile_pthread_getspecific(",
+        "fragile_pthread_key_delete(",
+    ] {
+        assert!(
+            rust_code.contains(&format!("fragile_runtime::{}", name)),
+            "{} should be called through fragile_runtime\nGenerated code:\n{}",
+            name,
+            &rust_code[..rust_code.len().min(3000)]
+        );
+    }
 }
 
 /// Test 23.9.1: Transpile minimal <iostream> usage
@@ -10384,164 +12772,741 @@ fn test_e2e_interval_ops() {
             initInterval(&b, 5, 7);
             if (intersect(&a, &b, &result)) return 17;  // No intersection
 
-            // Test unite
-            initInterval(&a, 1, 5);
-            initInterval(&b, 3, 7);
-            unite(&a, &b, &result);
-            if (result.start != 1 || result.end != 7) return 18;
+            // Test unite
+            initInterval(&a, 1, 5);
+            initInterval(&b, 3, 7);
+            unite(&a, &b, &result);
+            if (result.start != 1 || result.end != 7) return 18;
+
+            // Test adjacent
+            initInterval(&a, 1, 5);
+            initInterval(&b, 5, 10);
+            if (!adjacent(&a, &b)) return 19;
+
+            initInterval(&a, 1, 5);
+            initInterval(&b, 6, 10);
+            if (adjacent(&a, &b)) return 20;
+
+            return 0;  // Success
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_interval_ops.cpp").expect("E2E test failed");
+
+    assert_eq!(exit_code, 0, "Interval operations should work correctly");
+}
+
+/// Test abstract class with inherited method through concrete derived class.
+/// When Circle inherits draw() from abstract Shape without overriding it,
+/// the vtable wrapper must call through __base to access the inherited method.
+#[test]
+fn test_e2e_abstract_class_inherited_method() {
+    let source = r#"
+        // Abstract base class
+        class Shape {
+        public:
+            virtual double area() = 0;  // Pure virtual
+
+            // Non-pure virtual with default implementation
+            virtual int draw() {
+                return 100;  // Default implementation
+            }
+
+            virtual ~Shape() {}
+        };
+
+        // Circle inherits draw() without overriding
+        class Circle : public Shape {
+            double radius;
+        public:
+            Circle(double r) : radius(r) {}
+
+            // Only override area(), inherit draw()
+            double area() override {
+                return radius * radius * 3;  // Simplified pi
+            }
+        };
+
+        // Square overrides both
+        class Square : public Shape {
+            double side;
+        public:
+            Square(double s) : side(s) {}
+
+            double area() override {
+                return side * side;
+            }
+
+            int draw() override {
+                return 200;  // Override
+            }
+        };
+
+        int main() {
+            Circle c(2.0);  // area = 4*3 = 12
+            Square s(3.0);  // area = 9
+
+            // Test direct calls
+            if (c.area() < 11 || c.area() > 13) return 1;
+            if (s.area() < 8 || s.area() > 10) return 2;
+
+            // Test inherited draw() through Circle
+            if (c.draw() != 100) return 3;
+
+            // Test overridden draw() through Square
+            if (s.draw() != 200) return 4;
+
+            // Test polymorphic calls through base pointer
+            Shape* shapes[2];
+            shapes[0] = &c;
+            shapes[1] = &s;
+
+            // Polymorphic area()
+            double a0 = shapes[0]->area();
+            double a1 = shapes[1]->area();
+            if (a0 < 11 || a0 > 13) return 5;
+            if (a1 < 8 || a1 > 10) return 6;
+
+            // Polymorphic draw() - THIS IS THE KEY TEST
+            // shapes[0] is Circle which inherits draw() from abstract Shape
+            // shapes[1] is Square which overrides draw()
+            int d0 = shapes[0]->draw();
+            int d1 = shapes[1]->draw();
+            if (d0 != 100) return 7;  // Circle inherits default draw()
+            if (d1 != 200) return 8;  // Square overrides draw()
+
+            return 0;  // Success
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_abstract_class_inherited.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Abstract class with inherited method should work correctly"
+    );
+}
+
+/// Test dereferencing a post-incremented pointer.
+/// The pattern *ptr++ should:
+/// 1. Return the value at the original pointer location
+/// 2. Then increment the pointer
+/// This tests the fix for the unary operator parsing bug where
+/// nested operators like *ptr++ were incorrectly parsed.
+#[test]
+fn test_e2e_deref_postinc() {
+    let source = r#"
+        char get_and_advance(const char** ptr_ptr) {
+            const char* ptr = *ptr_ptr;
+            char result = *ptr++;  // Deref then post-increment
+            *ptr_ptr = ptr;
+            return result;
+        }
+
+        int main() {
+            const char* hello = "hello";
+            const char** pp = &hello;
+
+            // Get first char
+            char c1 = get_and_advance(pp);
+            if (c1 != 'h') return 1;
+
+            // Get second char
+            char c2 = get_and_advance(pp);
+            if (c2 != 'e') return 2;
+
+            // Get third char
+            char c3 = get_and_advance(pp);
+            if (c3 != 'l') return 3;
+
+            return 0;  // Success
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_deref_postinc.cpp").expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "Dereference of post-increment should work correctly"
+    );
+}
+
+/// E2E test: std::optional<T> maps to Rust Option<T>
+/// NOTE: This test is ignored because including <optional> header pulls in STL internals
+/// that generate invalid Rust code. The Option lowering itself is covered by the
+/// test_std_optional_maps_to_option unit test in ast_codegen.
+#[test]
+#[ignore]
+fn test_e2e_std_optional() {
+    let source = r#"
+        #include <optional>
+
+        int get_or(std::optional<int> o, int fallback) {
+            return o.value_or(fallback);
+        }
+
+        int main() {
+            std::optional<int> o = 5;
+            if (!o.has_value()) return 1;
+            if (*o != 5) return 2;
+            if (o.value() != 5) return 3;
+
+            std::optional<int> empty = std::nullopt;
+            if (empty.has_value()) return 4;
+            if (get_or(empty, 7) != 7) return 5;
+
+            o = std::nullopt;
+            if (o.has_value()) return 6;
+
+            o = 9;
+            if (o.has_value()) return *o - 9;
+            return 7;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_std_optional.cpp").expect("E2E test failed");
+
+    assert_eq!(exit_code, 0, "std::optional should map to Option correctly");
+}
+
+/// E2E test: std::to_array builds a std::array stub that compares element-wise
+/// NOTE: This test is ignored because including <array> header pulls in STL internals
+/// that generate invalid Rust code. The lowering itself is covered by the
+/// test_std_to_array_and_array_comparison unit test in ast_codegen.
+#[test]
+#[ignore]
+fn test_e2e_std_to_array() {
+    let source = r#"
+        #include <array>
+
+        int main() {
+            auto a = std::to_array({1, 2, 3});
+            if (!(a == std::to_array({1, 2, 3}))) return 1;
+            if (a != std::to_array({1, 2, 3})) return 2;
+
+            std::array<int, 3> b = {1, 2};
+            if (b == a) return 3;
+            b[2] = 3;
+            if (b != a) return 4;
+            return a.size() == 3 ? 0 : 5;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_std_to_array.cpp").expect("E2E test failed");
+
+    assert_eq!(exit_code, 0, "std::to_array results should compare equal");
+}
+
+/// E2E test: std::pair maps to a stub struct with first/second fields
+/// NOTE: This test is ignored because including <utility> header pulls in STL internals
+/// that generate invalid Rust code. The lowering itself is covered by the
+/// test_std_make_pair_and_member_access unit test in ast_codegen.
+#[test]
+#[ignore]
+fn test_e2e_std_pair() {
+    let source = r#"
+        #include <utility>
+
+        std::pair<int, double> make() {
+            return std::make_pair(4, 0.5);
+        }
+
+        int main() {
+            std::pair<int, double> p = make();
+            if (p.first != 4) return 1;
+            if (p.second != 0.5) return 2;
+            p.first = 7;
+            std::pair<int, double> q(7, 0.5);
+            if (p != q) return 3;
+            return p.first + (int)(p.second * 2) == 8 ? 0 : 4;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_std_pair.cpp").expect("E2E test failed");
+
+    assert_eq!(exit_code, 0, "std::pair should expose first/second fields");
+}
+
+/// E2E test: uppercasing a std::string in place through s[i] = c
+/// NOTE: This test is ignored because including <string> header pulls in STL internals
+/// that generate invalid Rust code. The stub side is covered by
+/// test_e2e_std_string_index_write and the lowering by the
+/// test_std_string_index_write_uses_op_index_mut unit test in ast_codegen.
+#[test]
+#[ignore]
+fn test_e2e_std_string_uppercase_in_place() {
+    let source = r#"
+        #include <string>
+
+        int main() {
+            std::string s("hello");
+            for (unsigned i = 0; i < s.size(); i++) {
+                if (s[i] >= 'a' && s[i] <= 'z') {
+                    s[i] = s[i] - 'a' + 'A';
+                }
+            }
+            return s == "HELLO" ? 0 : 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_std_string_uppercase_in_place.cpp")
+            .expect("E2E test failed");
+
+    assert_eq!(exit_code, 0, "s[i] = c should modify the string in place");
+}
+
+/// E2E test: structured bindings destructure std::pair's first/second
+/// NOTE: This test is ignored because including <utility> header pulls in STL internals
+/// that generate invalid Rust code. The lowering itself is covered by the
+/// test_structured_bindings unit test in ast_codegen.
+#[test]
+#[ignore]
+fn test_e2e_structured_bindings_std_pair() {
+    let source = r#"
+        #include <utility>
+
+        int main() {
+            std::pair<int, int> p(2, 5);
+            auto [first, second] = p;
+            auto& [f, s] = p;
+            f = first * second;
+            return p.first == 10 && s == 5 ? 0 : 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_structured_bindings_std_pair.cpp")
+            .expect("E2E test failed");
+
+    assert_eq!(
+        exit_code, 0,
+        "structured bindings should destructure std::pair"
+    );
+}
+
+/// E2E test: reference structured bindings over std::tuple write through to its elements
+/// NOTE: This test is ignored because including <tuple> header pulls in STL internals
+/// that generate invalid Rust code. The lowering itself is covered by the
+/// test_tuple_structured_binding_by_reference unit test in ast_codegen.
+#[test]
+#[ignore]
+fn test_e2e_structured_bindings_std_tuple_ref() {
+    let source = r#"
+        #include <tuple>
+
+        int main() {
+            std::tuple<int, int, int> t = std::make_tuple(1, 2, 3);
+            auto& [a, b, c] = t;
+            a = b + c;
+            auto [x, y, z] = t;
+            return x == 5 && y == 2 && z == 3 ? 0 : 1;
+        }
+    "#;
+
+    let (exit_code, _stdout, _stderr) =
+        transpile_compile_run(source, "e2e_structured_bindings_std_tuple_ref.cpp")
+            .expect("E2E test failed");
 
-            // Test adjacent
-            initInterval(&a, 1, 5);
-            initInterval(&b, 5, 10);
-            if (!adjacent(&a, &b)) return 19;
+    assert_eq!(
+        exit_code, 0,
+        "reference structured bindings should mutate the std::tuple"
+    );
+}
 
-            initInterval(&a, 1, 5);
-            initInterval(&b, 6, 10);
-            if (adjacent(&a, &b)) return 20;
+/// E2E test: std::bitset<N> maps to a word-backed stub with population count
+/// NOTE: This test is ignored because including <bitset> header pulls in STL internals
+/// that generate invalid Rust code. The lowering itself is covered by the
+/// test_std_bitset_stub_and_overloaded_members unit test in ast_codegen.
+#[test]
+#[ignore]
+fn test_e2e_std_bitset() {
+    let source = r#"
+        #include <bitset>
 
-            return 0;  // Success
+        int main() {
+            std::bitset<32> b;
+            b.set(1);
+            b.set(4);
+            b.set(31);
+            b.flip(4);
+            if (!b.test(31) || b[4]) return 1;
+            if (b.none() || b.all()) return 2;
+            b.set();
+            b.reset(0);
+            return b.count() == 31 ? 0 : 3;
         }
     "#;
 
     let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_interval_ops.cpp").expect("E2E test failed");
+        transpile_compile_run(source, "e2e_std_bitset.cpp").expect("E2E test failed");
 
-    assert_eq!(exit_code, 0, "Interval operations should work correctly");
+    assert_eq!(
+        exit_code, 0,
+        "std::bitset count() should return the population count"
+    );
 }
 
-/// Test abstract class with inherited method through concrete derived class.
-/// When Circle inherits draw() from abstract Shape without overriding it,
-/// the vtable wrapper must call through __base to access the inherited method.
+/// Test: std::vector<T> stubs are generated per used element type.
+/// Vectors of double and of user structs get their own concrete stub, while
+/// element types that are never instantiated are not emitted.
 #[test]
-fn test_e2e_abstract_class_inherited_method() {
+fn test_std_vector_generic_stub_generation() {
+    let parser = ClangParser::new().expect("Failed to create parser");
+
     let source = r#"
-        // Abstract base class
-        class Shape {
-        public:
-            virtual double area() = 0;  // Pure virtual
+        #include <vector>
 
-            // Non-pure virtual with default implementation
-            virtual int draw() {
-                return 100;  // Default implementation
-            }
+        struct Point { int x; int y; };
 
-            virtual ~Shape() {}
-        };
+        int main() {
+            std::vector<double> ds;
+            ds.push_back(1.5);
+            std::vector<Point> ps;
+            ps.push_back(Point{1, 2});
+            return ds.size() + ps.size() == 2 ? 0 : 1;
+        }
+    "#;
 
-        // Circle inherits draw() without overriding
-        class Circle : public Shape {
-            double radius;
-        public:
-            Circle(double r) : radius(r) {}
+    let ast = parser
+        .parse_string(source, "vector_generic.cpp")
+        .expect("Failed to parse");
+    let rust_code = AstCodeGen::new().generate(&ast.translation_unit);
 
-            // Only override area(), inherit draw()
-            double area() override {
-                return radius * radius * 3;  // Simplified pi
-            }
-        };
+    assert!(
+        rust_code.contains("pub struct std_vector_double {"),
+        "std::vector<double> should get its own stub"
+    );
+    assert!(
+        rust_code.contains("std::alloc::Layout::array::<f64>(new_cap)"),
+        "std::vector<double> stub should allocate f64 elements"
+    );
+    assert!(
+        rust_code.contains("pub struct std_vector_Point {"),
+        "std::vector<Point> should get its own stub"
+    );
+    // The i32 specialization is always present
+    assert!(rust_code.contains("pub struct std_vector_int {"));
+    assert!(
+        !rust_code.contains("pub struct std_vector_bool {"),
+        "Unused vector instantiations should not be generated"
+    );
+}
 
-        // Square overrides both
-        class Square : public Shape {
-            double side;
-        public:
-            Square(double s) : side(s) {}
+/// Test: vector::emplace_back constructs the element from constructor arguments.
+#[test]
+fn test_std_vector_emplace_back() {
+    let parser = ClangParser::new().expect("Failed to create parser");
 
-            double area() override {
-                return side * side;
-            }
+    let source = r#"
+        #include <vector>
 
-            int draw() override {
-                return 200;  // Override
-            }
+        struct Point {
+            int x;
+            int y;
+            Point(int x, int y) : x(x), y(y) {}
         };
 
         int main() {
-            Circle c(2.0);  // area = 4*3 = 12
-            Square s(3.0);  // area = 9
+            std::vector<Point> ps;
+            ps.emplace_back(1, 2);
+            std::vector<int> xs;
+            xs.emplace_back(3);
+            return ps.size() + xs.size() == 2 ? 0 : 1;
+        }
+    "#;
 
-            // Test direct calls
-            if (c.area() < 11 || c.area() > 13) return 1;
-            if (s.area() < 8 || s.area() > 10) return 2;
+    let ast = parser
+        .parse_string(source, "vector_emplace_back.cpp")
+        .expect("Failed to parse");
+    let rust_code = AstCodeGen::new().generate(&ast.translation_unit);
 
-            // Test inherited draw() through Circle
-            if (c.draw() != 100) return 3;
+    assert!(
+        rust_code.contains("ps.emplace_back(Point::new_2("),
+        "emplace_back should construct the element with its constructor"
+    );
+    assert!(
+        rust_code.contains("xs.emplace_back(3"),
+        "emplace_back of a primitive should push the value"
+    );
+}
 
-            // Test overridden draw() through Square
-            if (s.draw() != 200) return 4;
+/// Test: std::move lowering. `std::move(s)` into a `std::string&&` parameter becomes a plain
+/// Rust move and `std::move(r)` out of a reference becomes `std::mem::take`, so each buffer is
+/// freed exactly once by the std_string Drop impl (a double free would abort the binary).
+#[test]
+fn test_std_move_string_into_function() {
+    let main_code = r#"
+// size_t consume(std::string&& s) { return s.size(); }
+fn consume(s: std_string) -> usize { s.size() }
+// size_t pass(std::string s) { return consume(std::move(s)); }
+fn pass(s: std_string) -> usize { consume(s) }
+// size_t relay(std::string& r) { return consume(std::move(r)); }
+fn relay(r: &mut std_string) -> usize { consume(std::mem::take(r)) }
 
-            // Test polymorphic calls through base pointer
-            Shape* shapes[2];
-            shapes[0] = &c;
-            shapes[1] = &s;
+fn main() {
+    let a = std_string::new_1(b"hello\0".as_ptr() as *const i8);
+    if pass(a) != 5 { std::process::exit(1); }
+    let mut b = std_string::new_1(b"abc\0".as_ptr() as *const i8);
+    if relay(&mut b) != 3 { std::process::exit(2); }
+    // Moved-from strings are left empty and still safe to drop
+    if b.size() != 0 { std::process::exit(3); }
+    if relay(&mut b) != 0 { std::process::exit(4); }
+    std::process::exit(0);
+}
+"#;
 
-            // Polymorphic area()
-            double a0 = shapes[0]->area();
-            double a1 = shapes[1]->area();
-            if (a0 < 11 || a0 > 13) return 5;
-            if (a1 < 8 || a1 > 10) return 6;
+    let exit_code = run_with_stub_preamble(AstCodeGen::new(), main_code, "e2e_std_move_string");
+    assert_eq!(
+        exit_code,
+        Some(0),
+        "std::move test failed with exit code {:?}",
+        exit_code
+    );
+}
 
-            // Polymorphic draw() - THIS IS THE KEY TEST
-            // shapes[0] is Circle which inherits draw() from abstract Shape
-            // shapes[1] is Square which overrides draw()
-            int d0 = shapes[0]->draw();
-            int d1 = shapes[1]->draw();
-            if (d0 != 100) return 7;  // Circle inherits default draw()
-            if (d1 != 200) return 8;  // Square overrides draw()
+/// Test: vector::front/back read through shared references and write through the _mut variants.
+#[test]
+fn test_std_vector_front_back() {
+    let parser = ClangParser::new().expect("Failed to create parser");
 
-            return 0;  // Success
+    let source = r#"
+        #include <vector>
+
+        int main() {
+            std::vector<int> v;
+            v.push_back(1);
+            v.push_back(2);
+            v.back() = 7;
+            v.front() += 1;
+            return v.front() + v.back() == 9 ? 0 : 1;
         }
     "#;
 
-    let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_abstract_class_inherited.cpp").expect("E2E test failed");
+    let ast = parser
+        .parse_string(source, "vector_front_back.cpp")
+        .expect("Failed to parse");
+    let rust_code = AstCodeGen::new().generate(&ast.translation_unit);
+
+    assert!(
+        rust_code.contains("*v.back_mut() = 7"),
+        "Assigning to back() should write through back_mut()"
+    );
+    assert!(
+        rust_code.contains("*v.front_mut() += 1"),
+        "Compound assignment to front() should write through front_mut()"
+    );
+    assert!(
+        rust_code.contains("*v.front()"),
+        "Reading front() should dereference the element reference"
+    );
+}
+
+/// Compile the stub preamble emitted for an empty translation unit together with `main_code`,
+/// run it, and return the exit code. No C++ parsing is needed.
+fn run_with_stub_preamble(codegen: AstCodeGen, main_code: &str, name: &str) -> Option<i32> {
+    use std::fs;
+    use std::process::Command;
+
+    let tu = fragile_clang::ClangNode {
+        kind: fragile_clang::ClangNodeKind::TranslationUnit,
+        children: vec![],
+        location: Default::default(),
+    };
+    let rust_code = format!("{}\n{}", codegen.generate(&tu), main_code);
+
+    let temp_dir = std::env::temp_dir().join("fragile_e2e_tests");
+    fs::create_dir_all(&temp_dir).expect("Failed to create temp dir");
+
+    let rs_path = temp_dir.join(format!("{}.rs", name));
+    fs::write(&rs_path, rust_code).expect("Failed to write Rust source");
+
+    let binary_path = temp_dir.join(name);
+    let compile_output = Command::new("rustc")
+        .arg(&rs_path)
+        .arg("-o")
+        .arg(&binary_path)
+        .arg("--edition=2021")
+        .output()
+        .expect("Failed to run rustc");
+
+    if !compile_output.status.success() {
+        panic!(
+            "rustc compilation failed:\nstdout: {}\nstderr: {}",
+            String::from_utf8_lossy(&compile_output.stdout),
+            String::from_utf8_lossy(&compile_output.stderr)
+        );
+    }
+
+    let run_output = Command::new(&binary_path)
+        .output()
+        .expect("Failed to run binary");
+    run_output.status.code()
+}
+
+/// Test std::string find/rfind/substr and npos in the generated std_string stub.
+#[test]
+fn test_e2e_std_string_find_stub() {
+    let main_code = r#"
+fn main() {
+    let s = std_string::new_1(b"hello world\0".as_ptr() as *const i8);
+
+    // find with C string, char and string needles
+    if s.find(b"o\0".as_ptr() as *const i8, 0) != 4 { std::process::exit(1); }
+    if s.find(b"o\0".as_ptr() as *const i8, 5) != 7 { std::process::exit(2); }
+    if s.find(119i8, 0) != 6 { std::process::exit(3); }
+    let world = std_string::new_1(b"world\0".as_ptr() as *const i8);
+    if s.find(&world, 0) != 6 { std::process::exit(4); }
+
+    // Failed searches return npos
+    if s.find(b"xyz\0".as_ptr() as *const i8, 0) != std_string::NPOS { std::process::exit(5); }
+    if s.find(b"o\0".as_ptr() as *const i8, 100) != std_string::NPOS { std::process::exit(6); }
+    if std_string::NPOS != usize::MAX { std::process::exit(7); }
+
+    // rfind with a defaulted npos position (evaluated by libclang as -1)
+    if s.rfind(b"o\0".as_ptr() as *const i8, 18446744073709551615u64) != 7 { std::process::exit(8); }
+    if s.rfind(b"o\0".as_ptr() as *const i8, 5) != 4 { std::process::exit(9); }
+    if s.rfind(b"q\0".as_ptr() as *const i8, 18446744073709551615u64) != std_string::NPOS {
+        std::process::exit(10);
+    }
+
+    // Empty needle matches at pos
+    if s.find(b"\0".as_ptr() as *const i8, 3) != 3 { std::process::exit(11); }
+
+    // substr clamps the length to the end of the string
+    let sub = s.substr(6, 18446744073709551615u64);
+    if sub.size() != 5 { std::process::exit(12); }
+    if sub.find(&world, 0) != 0 { std::process::exit(13); }
+    let sub2 = s.substr(0, 5);
+    if sub2.size() != 5 || sub2.rfind(108i8, 18446744073709551615u64) != 3 {
+        std::process::exit(14);
+    }
 
+    std::process::exit(0);
+}
+"#;
+
+    let exit_code =
+        run_with_stub_preamble(AstCodeGen::new(), main_code, "e2e_std_string_find_stub");
     assert_eq!(
-        exit_code, 0,
-        "Abstract class with inherited method should work correctly"
+        exit_code,
+        Some(0),
+        "std_string find test failed with exit code {:?}",
+        exit_code
     );
 }
 
-/// Test dereferencing a post-incremented pointer.
-/// The pattern *ptr++ should:
-/// 1. Return the value at the original pointer location
-/// 2. Then increment the pointer
-/// This tests the fix for the unary operator parsing bug where
-/// nested operators like *ptr++ were incorrectly parsed.
+/// Test the std_string (count, char) and character range constructors.
 #[test]
-fn test_e2e_deref_postinc() {
-    let source = r#"
-        char get_and_advance(const char** ptr_ptr) {
-            const char* ptr = *ptr_ptr;
-            char result = *ptr++;  // Deref then post-increment
-            *ptr_ptr = ptr;
-            return result;
-        }
+fn test_e2e_std_string_count_char_and_range_ctors() {
+    let main_code = r#"
+fn main() {
+    // std::string s(3, 'a');
+    let s = std_string::new_count_char((3i32 as u64) as usize, 97i8);
+    if s.size() != 3 { std::process::exit(1); }
+    let aaa = std_string::new_1(b"aaa\0".as_ptr() as *const i8);
+    if s.find(&aaa, 0) != 0 { std::process::exit(2); }
+    if unsafe { *s.c_str().add(3) } != 0 { std::process::exit(3); }
 
-        int main() {
-            const char* hello = "hello";
-            const char** pp = &hello;
+    // std::string r(text + 6, text + 11);
+    let text = b"hello world\0".as_ptr() as *const i8;
+    let r = std_string::new_range(unsafe { text.add(6) }, unsafe { text.add(11) });
+    let world = std_string::new_1(b"world\0".as_ptr() as *const i8);
+    if r.size() != 5 || r.find(&world, 0) != 0 { std::process::exit(4); }
 
-            // Get first char
-            char c1 = get_and_advance(pp);
-            if (c1 != 'h') return 1;
+    // Empty count and empty range
+    if !std_string::new_count_char(0, 120i8).empty() { std::process::exit(5); }
+    if !std_string::new_range(text, text).empty() { std::process::exit(6); }
 
-            // Get second char
-            char c2 = get_and_advance(pp);
-            if (c2 != 'e') return 2;
+    std::process::exit(0);
+}
+"#;
 
-            // Get third char
-            char c3 = get_and_advance(pp);
-            if (c3 != 'l') return 3;
+    let exit_code = run_with_stub_preamble(
+        AstCodeGen::new(),
+        main_code,
+        "e2e_std_string_count_char_and_range_ctors",
+    );
+    assert_eq!(
+        exit_code,
+        Some(0),
+        "std_string constructor test failed with exit code {:?}",
+        exit_code
+    );
+}
 
-            return 0;  // Success
-        }
-    "#;
+/// Test std_string operator[] writes: uppercase a string in place via s[i] = c.
+#[test]
+fn test_e2e_std_string_index_write() {
+    let main_code = r#"
+fn main() {
+    // std::string s("hello");
+    let mut s = std_string::new_1(b"hello\0".as_ptr() as *const i8);
+    // for (int i = 0; i < s.size(); i++) s[i] = s[i] - ('a' - 'A');
+    let mut i = 0i32;
+    while (i as usize) < s.size() {
+        *s.op_index_mut(i) = ((*s.op_index(i) as i32) - (97i32 - 65i32)) as i8;
+        i += 1;
+    }
+    let upper = std_string::new_1(b"HELLO\0".as_ptr() as *const i8);
+    if s.find(&upper, 0) != 0 || s.size() != 5 { std::process::exit(1); }
+    // s[0] += 1;
+    *s.op_index_mut(0) += 1i8;
+    if *s.op_index(0) != 73i8 { std::process::exit(2); }
+    // s[s.size()] is the terminating NUL
+    if *s.op_index(s.size()) != 0 { std::process::exit(3); }
+    if *std_string::new_0().op_index(0) != 0 { std::process::exit(4); }
+    std::process::exit(0);
+}
+"#;
 
-    let (exit_code, _stdout, _stderr) =
-        transpile_compile_run(source, "e2e_deref_postinc.cpp").expect("E2E test failed");
+    let exit_code =
+        run_with_stub_preamble(AstCodeGen::new(), main_code, "e2e_std_string_index_write");
+    assert_eq!(
+        exit_code,
+        Some(0),
+        "std_string index write test failed with exit code {:?}",
+        exit_code
+    );
+}
+
+/// Test that container stubs implement Index/IndexMut when operator traits are enabled.
+#[test]
+fn test_e2e_container_index_traits() {
+    let main_code = r#"
+fn main() {
+    let mut v = std_vector_int::new_0();
+    v.push_back(10);
+    v.push_back(20);
+    v.push_back(30);
+
+    // Rust indexing forwards to op_index/op_index_mut
+    if v[2] != 30 { std::process::exit(1); }
+    v[1] = 25;
+    if *v.op_index(1) != 25 { std::process::exit(2); }
+    v[0] += 1;
+    if v[0] != 11 { std::process::exit(3); }
+
+    let mut m = std_unordered_map_int_int::new_0();
+    m[7] = 70;
+    if m[7] != 70 { std::process::exit(4); }
+    if m.size() != 1 { std::process::exit(5); }
 
+    std::process::exit(0);
+}
+"#;
+    let codegen = AstCodeGen::new().with_operator_traits(true);
+    let exit_code = run_with_stub_preamble(codegen, main_code, "e2e_container_index_traits");
     assert_eq!(
-        exit_code, 0,
-        "Dereference of post-increment should work correctly"
+        exit_code,
+        Some(0),
+        "index traits test failed with exit code {:?}",
+        exit_code
     );
 }
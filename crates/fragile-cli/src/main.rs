@@ -1,15 +1,26 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use miette::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "fragile")]
 #[command(author, version, about = "C++ to Rust transpiler")]
 struct Cli {
+    /// How to report parse and codegen diagnostics: human-readable on stderr, or
+    /// one JSON object per line on stdout
+    #[arg(long, global = true, value_enum, default_value_t = MessageFormat::Human)]
+    message_format: MessageFormat,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum MessageFormat {
+    Human,
+    Json,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Transpile C++ files to Rust source code
@@ -45,6 +56,62 @@ enum Commands {
         /// system-installed libc++. Useful for consistent builds across systems.
         #[arg(long)]
         use_vendored_libcxx: bool,
+
+        /// Implement std::ops traits (Index/IndexMut) on container stubs,
+        /// so the generated code can be indexed with Rust `v[i]` syntax.
+        #[arg(long)]
+        operator_traits: bool,
+
+        /// Emit one const-generic struct (`struct S<const N: usize>`) per set of type
+        /// arguments for class templates with size parameters, instead of one per
+        /// instantiation.
+        #[arg(long)]
+        const_generics: bool,
+    },
+
+    /// Transpile a C++ file, compile the result with rustc, and run it
+    Run {
+        /// C++ source file to run
+        file: PathBuf,
+
+        /// Arguments passed to the program (after `--`)
+        #[arg(last = true)]
+        args: Vec<String>,
+
+        /// Include directories
+        #[arg(short = 'I', long)]
+        include: Vec<PathBuf>,
+
+        /// Preprocessor definitions
+        #[arg(short = 'D', long)]
+        define: Vec<String>,
+
+        /// Use libc++ (LLVM's C++ standard library) instead of libstdc++
+        #[arg(long)]
+        use_libcxx: bool,
+
+        /// Use vendored libc++ from vendor/llvm-project/libcxx/include/
+        #[arg(long)]
+        use_vendored_libcxx: bool,
+
+        /// Compile with optimizations (rustc -C opt-level=3)
+        #[arg(long)]
+        release: bool,
+    },
+
+    /// Remove the libraries and executables built for the configured targets
+    ///
+    /// Only each target's executable (`<name>`) or library (`lib<name>.a`,
+    /// `lib<name>.so`) is removed. Object files and generated Rust sources are
+    /// left in place, since their names are not derived from the configuration.
+    Clean {
+        /// Build configuration file
+        #[arg(long, default_value = "fragile.toml")]
+        config: PathBuf,
+
+        /// Directory the targets were built into
+        #[arg(long, default_value = "build")]
+        output_dir: PathBuf,
     },
 
     /// Parse C++ files and show AST information (deprecated, use 'transpile')
@@ -72,6 +139,103 @@ enum Commands {
     },
 }
 
+/// Create a parser for the given include paths and defines, optionally using libc++.
+fn create_parser(
+    include: &[PathBuf],
+    define: &[String],
+    use_libcxx: bool,
+    use_vendored_libcxx: bool,
+) -> Result<fragile_clang::ClangParser> {
+    let include_paths: Vec<String> = include
+        .iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect();
+
+    if use_vendored_libcxx {
+        // Use vendored libc++ from vendor/llvm-project/libcxx/include/
+        if !fragile_clang::ClangParser::is_vendored_libcxx_available() {
+            return Err(miette::miette!(
+                "Vendored libc++ not found at vendor/llvm-project/libcxx/include/\n\
+                 Set FRAGILE_ROOT environment variable or run from the fragile project root."
+            ));
+        }
+        fragile_clang::ClangParser::with_vendored_libcxx_and_paths(include_paths)
+    } else if use_libcxx {
+        // Check if system libc++ is available
+        if !fragile_clang::ClangParser::is_libcxx_available() {
+            return Err(miette::miette!(
+                "libc++ not found. Please install it:\n  Debian/Ubuntu: apt install libc++-dev libc++abi-dev"
+            ));
+        }
+        let system_paths = fragile_clang::ClangParser::detect_libcxx_include_paths();
+        fragile_clang::ClangParser::with_full_options(
+            include_paths,
+            system_paths,
+            define.to_vec(),
+            Vec::new(),
+            true,
+        )
+    } else {
+        fragile_clang::ClangParser::with_paths_and_defines(
+            include_paths,
+            Vec::new(),
+            define.to_vec(),
+        )
+    }
+    .map_err(|e| miette::miette!("Failed to create parser: {}", e))
+}
+
+/// Parse a C++ file. In JSON mode, each error is written to stdout as a JSON line
+/// (see `fragile_common::Diagnostic::to_json`) and the process exits with status 1,
+/// without also rendering the errors on stderr.
+fn parse_file(
+    parser: &fragile_clang::ClangParser,
+    file: &Path,
+    message_format: MessageFormat,
+) -> Result<fragile_clang::ClangAst> {
+    parser.parse_file(file).map_err(|e| {
+        if message_format == MessageFormat::Json {
+            match e.downcast_ref::<fragile_clang::ClangErrors>() {
+                Some(errors) => {
+                    for diag in &errors.diagnostics {
+                        println!("{}", diag.to_json());
+                    }
+                }
+                None => println!(
+                    "{}",
+                    fragile_common::Diagnostic::error(e.to_string()).to_json()
+                ),
+            }
+            std::process::exit(1);
+        }
+        miette::miette!("Failed to parse {}: {}", file.display(), e)
+    })
+}
+
+/// Build directory of `fragile run`, removed when dropped unless `keep` is set.
+struct RunDir {
+    path: PathBuf,
+    keep: bool,
+}
+
+impl Drop for RunDir {
+    fn drop(&mut self) {
+        if !self.keep {
+            let _ = std::fs::remove_dir_all(&self.path);
+        }
+    }
+}
+
+/// Print codegen diagnostics: as text on stderr, or as JSON lines on stdout.
+fn report_diagnostics(diagnostics: &[fragile_common::Diagnostic], message_format: MessageFormat) {
+    for diag in diagnostics {
+        match message_format {
+            MessageFormat::Human => eprintln!("{}", diag.to_text()),
+            MessageFormat::Json => println!("{}", diag.to_json()),
+        }
+    }
+}
+
 fn main() -> Result<()> {
     miette::set_hook(Box::new(|_| {
         Box::new(
@@ -94,60 +258,28 @@ fn main() -> Result<()> {
             stubs_only,
             use_libcxx,
             use_vendored_libcxx,
+            operator_traits,
+            const_generics,
         } => {
-            let include_paths: Vec<String> = include
-                .iter()
-                .map(|p| p.to_string_lossy().to_string())
-                .collect();
-
             // Create parser with optional libc++ support
-            let parser = if use_vendored_libcxx {
-                // Use vendored libc++ from vendor/llvm-project/libcxx/include/
-                if !fragile_clang::ClangParser::is_vendored_libcxx_available() {
-                    return Err(miette::miette!(
-                        "Vendored libc++ not found at vendor/llvm-project/libcxx/include/\n\
-                         Set FRAGILE_ROOT environment variable or run from the fragile project root."
-                    ));
-                }
-                fragile_clang::ClangParser::with_vendored_libcxx_and_paths(include_paths)
-            } else if use_libcxx {
-                // Check if system libc++ is available
-                if !fragile_clang::ClangParser::is_libcxx_available() {
-                    return Err(miette::miette!(
-                        "libc++ not found. Please install it:\n  Debian/Ubuntu: apt install libc++-dev libc++abi-dev"
-                    ));
-                }
-                let system_paths = fragile_clang::ClangParser::detect_libcxx_include_paths();
-                fragile_clang::ClangParser::with_full_options(
-                    include_paths,
-                    system_paths,
-                    define.clone(),
-                    Vec::new(),
-                    true,
-                )
-            } else {
-                fragile_clang::ClangParser::with_paths_and_defines(
-                    include_paths,
-                    Vec::new(),
-                    define.clone(),
-                )
-            }
-            .map_err(|e| miette::miette!("Failed to create parser: {}", e))?;
+            let parser = create_parser(&include, &define, use_libcxx, use_vendored_libcxx)?;
 
             let mut all_output = String::new();
 
             for file in &files {
                 eprintln!("Transpiling: {}", file.display());
 
-                let ast = parser
-                    .parse_file(file)
-                    .map_err(|e| miette::miette!("Failed to parse {}: {}", file.display(), e))?;
+                let ast = parse_file(&parser, file, cli.message_format)?;
 
-                let code = if stubs_only {
-                    fragile_clang::AstCodeGen::new().generate_stubs(&ast.translation_unit)
+                let codegen = fragile_clang::AstCodeGen::new()
+                    .with_operator_traits(operator_traits)
+                    .with_const_generics(const_generics);
+                let (code, diagnostics) = if stubs_only {
+                    codegen.generate_stubs_with_diagnostics(&ast.translation_unit)
                 } else {
-                    fragile_clang::AstCodeGen::new().generate(&ast.translation_unit)
+                    codegen.generate_with_diagnostics(&ast.translation_unit)
                 };
+                report_diagnostics(&diagnostics, cli.message_format);
 
                 all_output.push_str(&code);
                 all_output.push('\n');
@@ -168,6 +300,101 @@ fn main() -> Result<()> {
             }
         }
 
+        Commands::Run {
+            file,
+            args,
+            include,
+            define,
+            use_libcxx,
+            use_vendored_libcxx,
+            release,
+        } => {
+            let parser = create_parser(&include, &define, use_libcxx, use_vendored_libcxx)?;
+            let ast = parse_file(&parser, &file, cli.message_format)?;
+            let (code, diagnostics) =
+                fragile_clang::AstCodeGen::new().generate_with_diagnostics(&ast.translation_unit);
+            report_diagnostics(&diagnostics, cli.message_format);
+
+            // Build in a per-process temp directory so concurrent runs don't collide
+            let stem = file
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| "main".to_string());
+            let mut build_dir = RunDir {
+                path: std::env::temp_dir().join(format!("fragile_run_{}", std::process::id())),
+                keep: false,
+            };
+            std::fs::create_dir_all(&build_dir.path)
+                .map_err(|e| miette::miette!("Failed to create build dir: {}", e))?;
+            let rs_path = build_dir.path.join(format!("{}.rs", stem));
+            let binary_path = build_dir.path.join(&stem);
+            std::fs::write(&rs_path, &code)
+                .map_err(|e| miette::miette!("Failed to write {}: {}", rs_path.display(), e))?;
+
+            // rustc's diagnostics go straight to our stderr
+            let mut rustc = std::process::Command::new("rustc");
+            rustc
+                .arg("--edition=2021")
+                .arg(&rs_path)
+                .arg("-o")
+                .arg(&binary_path);
+            if release {
+                rustc.arg("-C").arg("opt-level=3");
+            }
+            let compiled = rustc
+                .status()
+                .map_err(|e| miette::miette!("Failed to run rustc: {}", e))?;
+            if !compiled.success() {
+                build_dir.keep = true;
+                return Err(miette::miette!(
+                    "Compiling the transpiled {} failed (generated source kept at {})",
+                    file.display(),
+                    rs_path.display()
+                ));
+            }
+
+            // The program inherits stdio, so its output is streamed as it runs
+            let status = std::process::Command::new(&binary_path)
+                .args(&args)
+                .status()
+                .map_err(|e| miette::miette!("Failed to run {}: {}", binary_path.display(), e))?;
+            // exit() skips destructors, so the directory is removed first
+            drop(build_dir);
+            // A program killed by a signal has no exit code
+            std::process::exit(status.code().unwrap_or(1));
+        }
+
+        Commands::Clean { config, output_dir } => {
+            let build_config = fragile_build::BuildConfig::from_file(&config)
+                .map_err(|e| miette::miette!("Failed to load {}: {}", config.display(), e))?;
+            if !output_dir.is_dir() {
+                return Err(miette::miette!(
+                    "Output directory {} does not exist",
+                    output_dir.display()
+                ));
+            }
+
+            // Only remove files a build of the configured targets produces, so
+            // anything else the user keeps in the output directory survives
+            let mut removed = 0;
+            for target in &build_config.targets {
+                let name = target.artifact_name().map_err(|e| {
+                    miette::miette!("Invalid target in {}: {}", config.display(), e)
+                })?;
+                let path = output_dir.join(name);
+                if !path.is_file() {
+                    continue;
+                }
+                std::fs::remove_file(&path)
+                    .map_err(|e| miette::miette!("Failed to remove {}: {}", path.display(), e))?;
+                eprintln!("Removed: {}", path.display());
+                removed += 1;
+            }
+            if removed == 0 {
+                eprintln!("Nothing to clean in {}", output_dir.display());
+            }
+        }
+
         // Legacy command - redirect to transpile
         Commands::ParseCpp {
             files,
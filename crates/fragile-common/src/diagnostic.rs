@@ -20,6 +20,26 @@ pub enum DiagnosticLevel {
     Hint,
 }
 
+impl DiagnosticLevel {
+    /// Lowercase name, as used in machine-readable output.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiagnosticLevel::Error => "error",
+            DiagnosticLevel::Warning => "warning",
+            DiagnosticLevel::Info => "info",
+            DiagnosticLevel::Hint => "hint",
+        }
+    }
+}
+
+/// File, line and column (1-based) a diagnostic points at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticLocation {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
 #[derive(Debug, Clone, Error, MietteDiagnostic)]
 #[error("{message}")]
 pub struct Diagnostic {
@@ -30,6 +50,7 @@ pub struct Diagnostic {
     pub label: String,
     #[help]
     pub help: Option<String>,
+    pub location: Option<DiagnosticLocation>,
 }
 
 impl Diagnostic {
@@ -40,6 +61,7 @@ impl Diagnostic {
             span: None,
             label: String::new(),
             help: None,
+            location: None,
         }
     }
 
@@ -50,6 +72,18 @@ impl Diagnostic {
             span: None,
             label: String::new(),
             help: None,
+            location: None,
+        }
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            level: DiagnosticLevel::Info,
+            message: message.into(),
+            span: None,
+            label: String::new(),
+            help: None,
+            location: None,
         }
     }
 
@@ -70,4 +104,118 @@ impl Diagnostic {
         self.help = Some(help.into());
         self
     }
+
+    pub fn with_location(mut self, file: impl Into<String>, line: u32, column: u32) -> Self {
+        self.location = Some(DiagnosticLocation {
+            file: file.into(),
+            line,
+            column,
+        });
+        self
+    }
+
+    /// Render as a single human-readable line, e.g. `Warning: a.cpp:3:5: message`.
+    pub fn to_text(&self) -> String {
+        let level = match self.level {
+            DiagnosticLevel::Error => "Error",
+            DiagnosticLevel::Warning => "Warning",
+            DiagnosticLevel::Info => "Note",
+            DiagnosticLevel::Hint => "Hint",
+        };
+        match &self.location {
+            Some(loc) => format!(
+                "{}: {}:{}:{}: {}",
+                level, loc.file, loc.line, loc.column, self.message
+            ),
+            None => format!("{}: {}", level, self.message),
+        }
+    }
+
+    /// Serialize as a single-line JSON object with `level`, `message`, `file`,
+    /// `line`, `column` and `span` (`{"offset", "length"}`) fields. Missing
+    /// location or span fields are `null`.
+    pub fn to_json(&self) -> String {
+        let (file, line, column) = match &self.location {
+            Some(loc) => (
+                json_string(&loc.file),
+                loc.line.to_string(),
+                loc.column.to_string(),
+            ),
+            None => ("null".to_string(), "null".to_string(), "null".to_string()),
+        };
+        let span = match self.span {
+            Some(span) => format!("{{\"offset\":{},\"length\":{}}}", span.offset(), span.len()),
+            None => "null".to_string(),
+        };
+        format!(
+            "{{\"level\":\"{}\",\"message\":{},\"file\":{},\"line\":{},\"column\":{},\"span\":{}}}",
+            self.level.as_str(),
+            json_string(&self.message),
+            file,
+            line,
+            column,
+            span
+        )
+    }
+}
+
+/// Quote and escape a string as a JSON string literal.
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::SourceMap;
+
+    #[test]
+    fn test_to_json_with_location_and_span() {
+        let source = SourceMap::new()
+            .add_file("src/a.cpp", "int x = foo;".to_string())
+            .unwrap();
+        let diag = Diagnostic::error("unknown type \"foo\"")
+            .with_location("src/a.cpp", 3, 5)
+            .with_span(Span::new(source, 8, 11));
+        assert_eq!(
+            diag.to_json(),
+            "{\"level\":\"error\",\"message\":\"unknown type \\\"foo\\\"\",\"file\":\"src/a.cpp\",\
+             \"line\":3,\"column\":5,\"span\":{\"offset\":8,\"length\":3}}"
+        );
+    }
+
+    #[test]
+    fn test_to_json_without_location() {
+        let diag = Diagnostic::warning("tab\there\nnext");
+        assert_eq!(
+            diag.to_json(),
+            "{\"level\":\"warning\",\"message\":\"tab\\there\\nnext\",\"file\":null,\
+             \"line\":null,\"column\":null,\"span\":null}"
+        );
+    }
+
+    #[test]
+    fn test_to_text() {
+        assert_eq!(
+            Diagnostic::info("type punning")
+                .with_location("b.cpp", 7, 2)
+                .to_text(),
+            "Note: b.cpp:7:2: type punning"
+        );
+        assert_eq!(Diagnostic::error("boom").to_text(), "Error: boom");
+    }
 }
@@ -120,4 +120,14 @@ impl SourceMap {
         let id = path_to_id.get(path.as_ref())?;
         self.get(*id)
     }
+
+    /// Snapshot of all files, in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (SourceId, SourceFile)> {
+        let files = self.files.read().unwrap();
+        files
+            .iter()
+            .map(|file| (file.id, file.clone()))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
 }
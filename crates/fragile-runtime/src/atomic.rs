@@ -704,6 +704,91 @@ pub extern "C" fn fragile_atomic_compare_exchange_strong_bool(
     }
 }
 
+// ============================================================================
+// Typed compare-exchange (for __atomic_compare_exchange_n)
+// ============================================================================
+
+/// Atomic compare-exchange for i32 values (`__atomic_compare_exchange_n`).
+/// Returns true if successful. Updates expected on failure.
+#[no_mangle]
+pub extern "C" fn fragile_atomic_compare_exchange_i32(
+    ptr: *mut i32,
+    expected: *mut i32,
+    desired: i32,
+    success_order: i32,
+    failure_order: i32,
+) -> bool {
+    unsafe {
+        let atomic = &*(ptr as *const std::sync::atomic::AtomicI32);
+        match atomic.compare_exchange(
+            *expected,
+            desired,
+            to_ordering(success_order),
+            to_failure_ordering(failure_order),
+        ) {
+            Ok(_) => true,
+            Err(actual) => {
+                *expected = actual;
+                false
+            }
+        }
+    }
+}
+
+/// Atomic compare-exchange for i64 values (`__atomic_compare_exchange_n`).
+/// Returns true if successful. Updates expected on failure.
+#[no_mangle]
+pub extern "C" fn fragile_atomic_compare_exchange_i64(
+    ptr: *mut i64,
+    expected: *mut i64,
+    desired: i64,
+    success_order: i32,
+    failure_order: i32,
+) -> bool {
+    unsafe {
+        let atomic = &*(ptr as *const std::sync::atomic::AtomicI64);
+        match atomic.compare_exchange(
+            *expected,
+            desired,
+            to_ordering(success_order),
+            to_failure_ordering(failure_order),
+        ) {
+            Ok(_) => true,
+            Err(actual) => {
+                *expected = actual;
+                false
+            }
+        }
+    }
+}
+
+/// Atomic compare-exchange for usize values (`__atomic_compare_exchange_n`).
+/// Returns true if successful. Updates expected on failure.
+#[no_mangle]
+pub extern "C" fn fragile_atomic_compare_exchange_usize(
+    ptr: *mut usize,
+    expected: *mut usize,
+    desired: usize,
+    success_order: i32,
+    failure_order: i32,
+) -> bool {
+    unsafe {
+        let atomic = &*(ptr as *const std::sync::atomic::AtomicUsize);
+        match atomic.compare_exchange(
+            *expected,
+            desired,
+            to_ordering(success_order),
+            to_failure_ordering(failure_order),
+        ) {
+            Ok(_) => true,
+            Err(actual) => {
+                *expected = actual;
+                false
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -755,6 +840,64 @@ mod tests {
         assert_eq!(expected, 100); // expected updated to actual value
     }
 
+    #[test]
+    fn test_atomic_typed_compare_exchange() {
+        let mut value: i32 = -5;
+        let mut expected: i32 = -5;
+
+        // Should succeed - expected matches
+        assert!(fragile_atomic_compare_exchange_i32(
+            &mut value,
+            &mut expected,
+            7,
+            4, // success: acq_rel
+            2, // failure: acquire
+        ));
+        assert_eq!(value, 7);
+        assert_eq!(expected, -5);
+
+        // Should fail - expected doesn't match, observed value written back
+        assert!(!fragile_atomic_compare_exchange_i32(
+            &mut value,
+            &mut expected,
+            9,
+            5,
+            5
+        ));
+        assert_eq!(value, 7);
+        assert_eq!(expected, 7);
+
+        let mut wide: i64 = 1 << 40;
+        let mut wide_expected: i64 = 0;
+        assert!(!fragile_atomic_compare_exchange_i64(
+            &mut wide,
+            &mut wide_expected,
+            1,
+            5,
+            5
+        ));
+        assert_eq!(wide_expected, 1 << 40);
+        assert!(fragile_atomic_compare_exchange_i64(
+            &mut wide,
+            &mut wide_expected,
+            1,
+            5,
+            5
+        ));
+        assert_eq!(wide, 1);
+
+        let mut size: usize = 3;
+        let mut size_expected: usize = 3;
+        assert!(fragile_atomic_compare_exchange_usize(
+            &mut size,
+            &mut size_expected,
+            usize::MAX,
+            0,
+            0
+        ));
+        assert_eq!(size, usize::MAX);
+    }
+
     #[test]
     fn test_atomic_32_fetch_add() {
         let mut value: u32 = 42;
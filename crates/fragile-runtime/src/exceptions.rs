@@ -5,6 +5,19 @@
 //! platforms.
 
 use core::ffi::c_void;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "std")]
+use std::boxed::Box;
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
 #[cfg(feature = "std")]
 use std::cell::RefCell;
@@ -39,10 +52,38 @@ impl CppException {
     }
 }
 
+/// Reference-counted handle to an exception object (`std::exception_ptr`).
+///
+/// The exception's destructor runs when the last handle is released, so a
+/// captured exception outlives the catch block that captured it.
+#[repr(C)]
+pub struct ExceptionPtr {
+    /// Number of live references to this handle
+    refcount: AtomicUsize,
+    /// The exception object (type info, data and destructor)
+    exception: CppException,
+}
+
+impl ExceptionPtr {
+    /// Wrap an exception in a new handle holding one reference.
+    fn new(exception: CppException) -> *mut ExceptionPtr {
+        Box::into_raw(Box::new(ExceptionPtr {
+            refcount: AtomicUsize::new(1),
+            exception,
+        }))
+    }
+}
+
 /// Thread-local exception handling state.
 struct ExceptionState {
     /// Current exception (if any)
     current_exception: Option<CppException>,
+    /// Handle owning the current exception when it was rethrown from an exception_ptr
+    current_handle: *mut ExceptionPtr,
+    /// Handles to the exceptions being handled, innermost catch block last.
+    /// A try/catch inside a handler pushes its own entry, so the outer handler's
+    /// exception stays alive until that handler ends.
+    caught_exceptions: Vec<*mut ExceptionPtr>,
     /// Stack of try block handlers
     try_stack_depth: usize,
     /// Whether we're currently unwinding
@@ -53,10 +94,36 @@ impl ExceptionState {
     const fn new() -> Self {
         Self {
             current_exception: None,
+            current_handle: core::ptr::null_mut(),
+            caught_exceptions: Vec::new(),
             try_stack_depth: 0,
             unwinding: false,
         }
     }
+
+    /// Take the in-flight exception as a handle, wrapping it if no handle owns it yet.
+    fn take_current_handle(&mut self) -> *mut ExceptionPtr {
+        let exception = self.current_exception.take();
+        let handle = core::mem::replace(&mut self.current_handle, core::ptr::null_mut());
+        match exception {
+            Some(_) if !handle.is_null() => handle,
+            Some(ex) => ExceptionPtr::new(ex),
+            None => core::ptr::null_mut(),
+        }
+    }
+}
+
+/// Run `f` with the exception state of the current thread.
+fn with_state<R>(f: impl FnOnce(&mut ExceptionState) -> R) -> R {
+    #[cfg(feature = "std")]
+    {
+        EXCEPTION_STATE.with(|state| f(&mut state.borrow_mut()))
+    }
+
+    #[cfg(not(feature = "std"))]
+    unsafe {
+        f(&mut *core::ptr::addr_of_mut!(EXCEPTION_STATE))
+    }
 }
 
 #[cfg(feature = "std")]
@@ -74,25 +141,17 @@ pub fn init_exception_handling() {
 
 /// Clean up exception handling (called by fragile_rt_shutdown).
 pub fn cleanup_exception_handling() {
-    #[cfg(feature = "std")]
-    {
-        EXCEPTION_STATE.with(|state| {
-            let mut state = state.borrow_mut();
-            if let Some(ex) = state.current_exception.take() {
-                // Call destructor if present
-                if let Some(dtor) = ex.destructor {
-                    unsafe { dtor(ex.data) };
-                }
-            }
-        });
-    }
-
-    #[cfg(not(feature = "std"))]
+    // Releasing the handles calls the destructors of exceptions nobody else captured
+    let (current, caught) = with_state(|state| {
+        (
+            state.take_current_handle(),
+            core::mem::take(&mut state.caught_exceptions),
+        )
+    });
     unsafe {
-        if let Some(ex) = EXCEPTION_STATE.current_exception.take() {
-            if let Some(dtor) = ex.destructor {
-                dtor(ex.data);
-            }
+        fragile_rt_exception_ptr_release(current);
+        for handle in caught {
+            fragile_rt_exception_ptr_release(handle);
         }
     }
 }
@@ -142,7 +201,12 @@ pub extern "C" fn fragile_rt_try_end() {
 ///
 /// This stores the exception and begins unwinding.
 #[no_mangle]
-pub extern "C" fn fragile_rt_throw(exception: CppException) -> ! {
+pub extern "C-unwind" fn fragile_rt_throw(exception: CppException) -> ! {
+    // A fresh exception is not owned by the handle of a previously rethrown one
+    let stale =
+        with_state(|state| core::mem::replace(&mut state.current_handle, core::ptr::null_mut()));
+    unsafe { fragile_rt_exception_ptr_release(stale) };
+
     #[cfg(feature = "std")]
     {
         EXCEPTION_STATE.with(|state| {
@@ -184,28 +248,39 @@ pub extern "C" fn fragile_rt_check_exception() -> bool {
 
 /// Catch the current exception.
 ///
-/// Returns the current exception and clears the exception state.
+/// Returns the current exception and clears the exception state. The runtime keeps
+/// owning the exception object: it stays alive until the matching `fragile_rt_end_catch`
+/// (or until the last `ExceptionPtr` captured from it is released), so the returned
+/// destructor must not be called by the handler. Every call must be paired with one
+/// `fragile_rt_end_catch`.
 #[no_mangle]
 pub extern "C" fn fragile_rt_catch() -> CppException {
-    #[cfg(feature = "std")]
-    {
-        EXCEPTION_STATE.with(|state| {
-            let mut state = state.borrow_mut();
-            state.unwinding = false;
-            state
-                .current_exception
-                .take()
-                .unwrap_or(CppException::null())
-        })
+    let handle = with_state(|state| {
+        state.unwinding = false;
+        let handle = state.take_current_handle();
+        state.caught_exceptions.push(handle);
+        handle
+    });
+    if handle.is_null() {
+        return CppException::null();
+    }
+    let ex = unsafe { &(*handle).exception };
+    CppException {
+        type_info: ex.type_info,
+        data: ex.data,
+        destructor: ex.destructor,
     }
+}
 
-    #[cfg(not(feature = "std"))]
-    unsafe {
-        EXCEPTION_STATE.unwinding = false;
-        EXCEPTION_STATE
-            .current_exception
-            .take()
-            .unwrap_or(CppException::null())
+/// End the innermost catch block.
+///
+/// Drops the handler's reference to the caught exception; the object is destroyed
+/// unless an `ExceptionPtr` captured with `fragile_rt_current_exception` still holds it.
+#[no_mangle]
+pub extern "C" fn fragile_rt_end_catch() {
+    let caught = with_state(|state| state.caught_exceptions.pop());
+    if let Some(caught) = caught {
+        unsafe { fragile_rt_exception_ptr_release(caught) };
     }
 }
 
@@ -213,7 +288,7 @@ pub extern "C" fn fragile_rt_catch() -> CppException {
 ///
 /// Used for `throw;` with no argument.
 #[no_mangle]
-pub extern "C" fn fragile_rt_rethrow() -> ! {
+pub extern "C-unwind" fn fragile_rt_rethrow() -> ! {
     #[cfg(feature = "std")]
     {
         let has_exception =
@@ -232,30 +307,102 @@ pub extern "C" fn fragile_rt_rethrow() -> ! {
     }
 }
 
-/// Get the current exception without catching it.
+/// Capture the exception being handled (`std::current_exception()`).
 ///
-/// Used for examining the exception in a catch block.
+/// Returns a new reference to the exception caught by the innermost catch block,
+/// or null outside a catch block. Release it with `fragile_rt_exception_ptr_release`.
 #[no_mangle]
-pub extern "C" fn fragile_rt_current_exception() -> *const CppException {
+pub extern "C" fn fragile_rt_current_exception() -> *mut ExceptionPtr {
+    let caught = with_state(|state| {
+        state
+            .caught_exceptions
+            .last()
+            .copied()
+            .unwrap_or(core::ptr::null_mut())
+    });
+    unsafe { fragile_rt_exception_ptr_retain(caught) }
+}
+
+/// Rethrow a captured exception (`std::rethrow_exception(p)`).
+///
+/// The exception re-enters the throw path with its original payload and type info;
+/// the handle keeps its own reference.
+///
+/// # Safety
+/// `ptr` must be a live handle returned by `fragile_rt_current_exception` or
+/// `fragile_rt_exception_ptr_retain`.
+#[no_mangle]
+pub unsafe extern "C-unwind" fn fragile_rt_rethrow_exception(ptr: *mut ExceptionPtr) -> ! {
+    if ptr.is_null() {
+        // Rethrowing a null exception_ptr is undefined behavior in C++
+        fragile_rt_rethrow();
+    }
+    let handle = fragile_rt_exception_ptr_retain(ptr);
+    let ex = &(*handle).exception;
+    let exception = CppException {
+        type_info: ex.type_info,
+        data: ex.data,
+        destructor: ex.destructor,
+    };
+    let previous = with_state(|state| {
+        state.current_exception = Some(exception);
+        state.unwinding = true;
+        core::mem::replace(&mut state.current_handle, handle)
+    });
+    fragile_rt_exception_ptr_release(previous);
+
     #[cfg(feature = "std")]
     {
-        EXCEPTION_STATE.with(|state| {
-            let state = state.borrow();
-            state
-                .current_exception
-                .as_ref()
-                .map(|ex| ex as *const CppException)
-                .unwrap_or(core::ptr::null())
-        })
+        panic!("C++ exception rethrown");
     }
 
     #[cfg(not(feature = "std"))]
-    unsafe {
-        EXCEPTION_STATE
-            .current_exception
-            .as_ref()
-            .map(|ex| ex as *const CppException)
-            .unwrap_or(core::ptr::null())
+    loop {}
+}
+
+/// Add a reference to an exception handle (copying a `std::exception_ptr`).
+///
+/// Returns `ptr` (null stays null).
+///
+/// # Safety
+/// `ptr` must be null or a live exception handle.
+#[no_mangle]
+pub unsafe extern "C" fn fragile_rt_exception_ptr_retain(
+    ptr: *mut ExceptionPtr,
+) -> *mut ExceptionPtr {
+    if !ptr.is_null() {
+        (*ptr).refcount.fetch_add(1, Ordering::Relaxed);
+    }
+    ptr
+}
+
+/// Drop a reference to an exception handle, destroying the exception with the last one.
+///
+/// # Safety
+/// `ptr` must be null or a live exception handle; it must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn fragile_rt_exception_ptr_release(ptr: *mut ExceptionPtr) {
+    if ptr.is_null() || (*ptr).refcount.fetch_sub(1, Ordering::AcqRel) != 1 {
+        return;
+    }
+    let handle = Box::from_raw(ptr);
+    if let Some(dtor) = handle.exception.destructor {
+        dtor(handle.exception.data);
+    }
+}
+
+/// Get the exception object behind a handle (for examining a captured exception).
+///
+/// # Safety
+/// `ptr` must be null or a live exception handle.
+#[no_mangle]
+pub unsafe extern "C" fn fragile_rt_exception_ptr_get(
+    ptr: *const ExceptionPtr,
+) -> *const CppException {
+    if ptr.is_null() {
+        core::ptr::null()
+    } else {
+        &(*ptr).exception
     }
 }
 
@@ -292,3 +439,96 @@ pub extern "C" fn fragile_rt_exception_matches(type_info: *const c_void) -> bool
         }
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    static DESTROYED: AtomicUsize = AtomicUsize::new(0);
+    static INT_TYPE: u8 = 0;
+
+    unsafe extern "C" fn destroy_int(data: *mut c_void) {
+        DESTROYED.fetch_add(1, Ordering::SeqCst);
+        drop(Box::from_raw(data as *mut i32));
+    }
+
+    #[test]
+    fn test_exception_ptr_rethrown_after_catch() {
+        let type_info = &INT_TYPE as *const u8 as *const c_void;
+        let exception = CppException {
+            type_info,
+            data: Box::into_raw(Box::new(7i32)) as *mut c_void,
+            destructor: Some(destroy_int),
+        };
+        let thrown = std::panic::catch_unwind(|| fragile_rt_throw(exception));
+        assert!(thrown.is_err());
+
+        // catch (...) { saved = std::current_exception(); }
+        let caught = fragile_rt_catch();
+        let saved = fragile_rt_current_exception();
+        assert!(!saved.is_null());
+        fragile_rt_end_catch();
+
+        // The handle keeps the exception alive after the catch block ends
+        assert_eq!(DESTROYED.load(Ordering::SeqCst), 0);
+        let stored = unsafe { &*fragile_rt_exception_ptr_get(saved) };
+        assert_eq!(stored.data, caught.data);
+        assert_eq!(unsafe { *(stored.data as *const i32) }, 7);
+
+        // std::rethrow_exception(saved) re-enters the throw path with the same payload
+        let rethrown = std::panic::catch_unwind(|| unsafe { fragile_rt_rethrow_exception(saved) });
+        assert!(rethrown.is_err());
+        assert!(fragile_rt_exception_matches(type_info));
+        let again = fragile_rt_catch();
+        assert_eq!(again.data, caught.data);
+        fragile_rt_end_catch();
+        assert_eq!(DESTROYED.load(Ordering::SeqCst), 0);
+
+        // Releasing the last reference destroys the exception object
+        unsafe { fragile_rt_exception_ptr_release(saved) };
+        assert_eq!(DESTROYED.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_nested_catch_keeps_outer_exception_alive() {
+        static NESTED_DESTROYED: AtomicUsize = AtomicUsize::new(0);
+        unsafe extern "C" fn destroy_nested(data: *mut c_void) {
+            NESTED_DESTROYED.fetch_add(1, Ordering::SeqCst);
+            drop(Box::from_raw(data as *mut i32));
+        }
+        let throw = |value: i32| {
+            let exception = CppException {
+                type_info: core::ptr::null(),
+                data: Box::into_raw(Box::new(value)) as *mut c_void,
+                destructor: Some(destroy_nested),
+            };
+            assert!(std::panic::catch_unwind(|| fragile_rt_throw(exception)).is_err());
+        };
+
+        // catch (int& outer) { try { throw 2; } catch (int) {} use(outer); }
+        throw(1);
+        let outer = fragile_rt_catch();
+        throw(2);
+        let inner = fragile_rt_catch();
+        assert_eq!(unsafe { *(inner.data as *const i32) }, 2);
+        fragile_rt_end_catch();
+
+        // Only the inner exception is destroyed; the outer handler still sees its own
+        assert_eq!(NESTED_DESTROYED.load(Ordering::SeqCst), 1);
+        assert_eq!(unsafe { *(outer.data as *const i32) }, 1);
+        let current = fragile_rt_current_exception();
+        assert_eq!(
+            unsafe { (*fragile_rt_exception_ptr_get(current)).data },
+            outer.data
+        );
+        unsafe { fragile_rt_exception_ptr_release(current) };
+        fragile_rt_end_catch();
+        assert_eq!(NESTED_DESTROYED.load(Ordering::SeqCst), 2);
+        assert!(fragile_rt_current_exception().is_null());
+    }
+
+    #[test]
+    fn test_current_exception_outside_catch_is_null() {
+        assert!(fragile_rt_current_exception().is_null());
+    }
+}
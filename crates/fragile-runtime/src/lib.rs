@@ -33,6 +33,7 @@
 //! if (fragile_rt_check_exception()) {
 //!     e = fragile_rt_catch();
 //!     handle(e);
+//!     fragile_rt_end_catch();
 //! }
 //! fragile_rt_try_end();
 //! ```
@@ -45,22 +46,30 @@ extern crate alloc;
 mod atomic;
 mod exceptions;
 mod memory;
+#[cfg(feature = "std")]
+mod printf_format;
 mod pthread;
 mod pthread_cond;
+mod pthread_key;
 mod pthread_mutex;
 mod pthread_rwlock;
 mod rtti;
+mod setjmp;
 mod stdio;
 mod vtable;
 
 pub use atomic::*;
 pub use exceptions::*;
 pub use memory::*;
+#[cfg(feature = "std")]
+pub use printf_format::PrintfArg;
 pub use pthread::*;
 pub use pthread_cond::*;
+pub use pthread_key::*;
 pub use pthread_mutex::*;
 pub use pthread_rwlock::*;
 pub use rtti::*;
+pub use setjmp::*;
 pub use stdio::*;
 pub use vtable::*;
 
@@ -12,6 +12,7 @@ extern "C" {
     fn free(ptr: *mut c_void);
     fn realloc(ptr: *mut c_void, size: usize) -> *mut c_void;
     fn calloc(nmemb: usize, size: usize) -> *mut c_void;
+    fn aligned_alloc(alignment: usize, size: usize) -> *mut c_void;
 }
 
 /// Allocate memory for a C++ object (operator new).
@@ -81,36 +82,88 @@ pub unsafe extern "C" fn fragile_rt_delete(ptr: *mut c_void, size: usize) {
     }
 }
 
+/// Space reserved in front of an array allocation; the element count is
+/// stored in the last word of it, directly before the first element.
+fn array_header_size(element_align: usize) -> usize {
+    element_align.max(core::mem::size_of::<usize>())
+}
+
 /// Allocate memory for a C++ array (operator new[]).
 ///
+/// The element count is recorded in a header so `fragile_rt_delete_array`
+/// can run every destructor and free the whole block. Returns the address of
+/// the first element, aligned to `element_align`.
+///
 /// # Safety
-/// Same as fragile_rt_new, but for arrays.
+/// `element_align` must be a power of two. The elements are uninitialized.
 #[no_mangle]
-pub unsafe extern "C" fn fragile_rt_new_array(count: usize, element_size: usize) -> *mut c_void {
-    let total_size = count.saturating_mul(element_size);
-    if total_size == 0 {
+pub unsafe extern "C" fn fragile_rt_new_array(
+    count: usize,
+    element_size: usize,
+    element_align: usize,
+) -> *mut c_void {
+    let header = array_header_size(element_align);
+    let total_size = match count
+        .checked_mul(element_size)
+        .and_then(|size| size.checked_add(header))
+    {
+        Some(size) => size,
+        None => return core::ptr::null_mut(),
+    };
+
+    #[cfg(feature = "std")]
+    let base = alloc(Layout::from_size_align_unchecked(total_size, header));
+
+    #[cfg(not(feature = "std"))]
+    let base = aligned_alloc(header, total_size.div_ceil(header) * header) as *mut u8;
+
+    if base.is_null() {
         return core::ptr::null_mut();
     }
 
-    fragile_rt_new(total_size)
+    let data = base.add(header);
+    *(data.sub(core::mem::size_of::<usize>()) as *mut usize) = count;
+    data as *mut c_void
 }
 
 /// Free memory for a C++ array (operator delete[]).
 ///
+/// Runs `destructor` on each element in reverse order (if given), then
+/// frees the block.
+///
 /// # Safety
-/// Same as fragile_rt_delete, but for arrays.
+/// Pointer must have been returned by fragile_rt_new_array with the same
+/// element size and alignment, and all elements must be initialized.
 #[no_mangle]
 pub unsafe extern "C" fn fragile_rt_delete_array(
     ptr: *mut c_void,
-    count: usize,
     element_size: usize,
+    element_align: usize,
+    destructor: Option<unsafe extern "C" fn(*mut c_void)>,
 ) {
     if ptr.is_null() {
         return;
     }
 
-    let total_size = count.saturating_mul(element_size);
-    fragile_rt_delete(ptr, total_size);
+    let header = array_header_size(element_align);
+    let data = ptr as *mut u8;
+    let count = *(data.sub(core::mem::size_of::<usize>()) as *const usize);
+    if let Some(destructor) = destructor {
+        fragile_rt_call_array_destructor(ptr, count, element_size, destructor);
+    }
+
+    let base = data.sub(header);
+
+    #[cfg(feature = "std")]
+    {
+        let layout = Layout::from_size_align_unchecked(count * element_size + header, header);
+        dealloc(base, layout);
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        free(base as *mut c_void);
+    }
 }
 
 /// Reallocate memory (for std::realloc compatibility).
@@ -197,6 +250,40 @@ pub unsafe extern "C" fn fragile_rt_call_array_destructor(
 // directly instead of operator new/delete in some cases.
 // ============================================================================
 
+/// Layouts of the live allocations handed out by the C allocation functions,
+/// keyed by address, so `fragile_free` and `fragile_realloc` can release a
+/// block with the size and alignment it was allocated with.
+#[cfg(feature = "std")]
+static ALLOCATIONS: std::sync::Mutex<std::collections::BTreeMap<usize, Layout>> =
+    std::sync::Mutex::new(std::collections::BTreeMap::new());
+
+/// Allocate a block and record its layout for `fragile_free`.
+#[cfg(feature = "std")]
+unsafe fn tracked_alloc(layout: Layout, zeroed: bool) -> *mut c_void {
+    let ptr = if zeroed {
+        alloc_zeroed(layout)
+    } else {
+        alloc(layout)
+    };
+    if !ptr.is_null() {
+        ALLOCATIONS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(ptr as usize, layout);
+    }
+    ptr as *mut c_void
+}
+
+/// Look up the layout recorded for a live allocation.
+#[cfg(feature = "std")]
+fn tracked_layout(ptr: *mut c_void) -> Option<Layout> {
+    ALLOCATIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&(ptr as usize))
+        .copied()
+}
+
 /// Standard C malloc - allocate memory.
 ///
 /// # Safety
@@ -209,8 +296,7 @@ pub unsafe extern "C" fn fragile_malloc(size: usize) -> *mut c_void {
 
     #[cfg(feature = "std")]
     {
-        let layout = Layout::from_size_align_unchecked(size, 8);
-        alloc(layout) as *mut c_void
+        tracked_alloc(Layout::from_size_align_unchecked(size, 8), false)
     }
 
     #[cfg(not(feature = "std"))]
@@ -222,7 +308,8 @@ pub unsafe extern "C" fn fragile_malloc(size: usize) -> *mut c_void {
 /// Standard C free - deallocate memory.
 ///
 /// # Safety
-/// Pointer must have been allocated by fragile_malloc, fragile_calloc, or fragile_realloc.
+/// Pointer must have been allocated by fragile_malloc, fragile_calloc, fragile_realloc,
+/// fragile_aligned_alloc or fragile_posix_memalign.
 #[no_mangle]
 pub unsafe extern "C" fn fragile_free(ptr: *mut c_void) {
     if ptr.is_null() {
@@ -231,10 +318,14 @@ pub unsafe extern "C" fn fragile_free(ptr: *mut c_void) {
 
     #[cfg(feature = "std")]
     {
-        // Note: We don't know the original size, so we use a minimal layout.
-        // This works for std::alloc because the allocator tracks sizes internally.
-        let layout = Layout::from_size_align_unchecked(1, 8);
-        dealloc(ptr as *mut u8, layout);
+        let layout = ALLOCATIONS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&(ptr as usize));
+        // A pointer we did not hand out (or a double free) is left alone
+        if let Some(layout) = layout {
+            dealloc(ptr as *mut u8, layout);
+        }
     }
 
     #[cfg(not(feature = "std"))]
@@ -260,14 +351,15 @@ pub unsafe extern "C" fn fragile_realloc(ptr: *mut c_void, new_size: usize) -> *
 
     #[cfg(feature = "std")]
     {
-        // For std::alloc, we need to know the old layout to realloc.
-        // Since we don't track sizes, we allocate new memory and copy.
-        // This is inefficient but correct.
+        // Allocate new memory and copy the part of the old block that still fits
+        let old_size = tracked_layout(ptr).map_or(0, |layout| layout.size());
         let new_ptr = fragile_malloc(new_size);
         if !new_ptr.is_null() {
-            // We don't know old size, so we copy conservatively.
-            // In practice, realloc is rarely used for shrinking.
-            core::ptr::copy_nonoverlapping(ptr as *const u8, new_ptr as *mut u8, new_size);
+            core::ptr::copy_nonoverlapping(
+                ptr as *const u8,
+                new_ptr as *mut u8,
+                old_size.min(new_size),
+            );
         }
         fragile_free(ptr);
         new_ptr
@@ -292,8 +384,7 @@ pub unsafe extern "C" fn fragile_calloc(nmemb: usize, size: usize) -> *mut c_voi
 
     #[cfg(feature = "std")]
     {
-        let layout = Layout::from_size_align_unchecked(total_size, 8);
-        alloc_zeroed(layout) as *mut c_void
+        tracked_alloc(Layout::from_size_align_unchecked(total_size, 8), true)
     }
 
     #[cfg(not(feature = "std"))]
@@ -302,6 +393,64 @@ pub unsafe extern "C" fn fragile_calloc(nmemb: usize, size: usize) -> *mut c_voi
     }
 }
 
+/// Standard C aligned_alloc - allocate memory with the given alignment.
+///
+/// Returns null if `alignment` is not a power of two or `size` is zero.
+///
+/// # Safety
+/// Returns a pointer that must be freed with fragile_free.
+#[no_mangle]
+pub unsafe extern "C" fn fragile_aligned_alloc(alignment: usize, size: usize) -> *mut c_void {
+    if size == 0 {
+        return core::ptr::null_mut();
+    }
+    let layout = match Layout::from_size_align(size, alignment) {
+        Ok(layout) => layout,
+        Err(_) => return core::ptr::null_mut(),
+    };
+
+    #[cfg(feature = "std")]
+    {
+        tracked_alloc(layout, false)
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        // C11 aligned_alloc wants the size to be a multiple of the alignment
+        aligned_alloc(alignment, layout.pad_to_align().size())
+    }
+}
+
+/// POSIX posix_memalign - allocate aligned memory, storing it in `*out`.
+///
+/// Returns 0 on success, EINVAL if `alignment` is not a power of two multiple of
+/// `sizeof(void*)`, or ENOMEM if the allocation fails.
+///
+/// # Safety
+/// `out` must be valid for writes. The stored pointer must be freed with fragile_free.
+#[no_mangle]
+pub unsafe extern "C" fn fragile_posix_memalign(
+    out: *mut *mut c_void,
+    alignment: usize,
+    size: usize,
+) -> i32 {
+    // A power of two is a multiple of sizeof(void*) exactly when it is at least that large
+    if !alignment.is_power_of_two() || alignment < core::mem::size_of::<*mut c_void>() {
+        return libc::EINVAL;
+    }
+    if size == 0 {
+        // POSIX allows either null or a unique pointer for a zero-size request
+        *out = core::ptr::null_mut();
+        return 0;
+    }
+    let ptr = fragile_aligned_alloc(alignment, size);
+    if ptr.is_null() {
+        return libc::ENOMEM;
+    }
+    *out = ptr;
+    0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -359,6 +508,89 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_array_new_delete_runs_destructors_in_reverse() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        static ORDER: [AtomicUsize; 4] = [
+            AtomicUsize::new(0),
+            AtomicUsize::new(0),
+            AtomicUsize::new(0),
+            AtomicUsize::new(0),
+        ];
+
+        #[repr(C, align(32))]
+        struct Tracked {
+            id: usize,
+        }
+
+        unsafe extern "C" fn destroy(ptr: *mut c_void) {
+            let slot = NEXT.fetch_add(1, Ordering::SeqCst);
+            ORDER[slot].store((*(ptr as *mut Tracked)).id, Ordering::SeqCst);
+        }
+
+        unsafe {
+            let size = core::mem::size_of::<Tracked>();
+            let align = core::mem::align_of::<Tracked>();
+            let ptr = fragile_rt_new_array(4, size, align) as *mut Tracked;
+            assert!(!ptr.is_null());
+            assert_eq!(ptr as usize % align, 0);
+            for i in 0..4 {
+                ptr.add(i).write(Tracked { id: i + 1 });
+            }
+
+            fragile_rt_delete_array(ptr as *mut c_void, size, align, Some(destroy));
+        }
+
+        assert_eq!(NEXT.load(Ordering::SeqCst), 4);
+        let order: Vec<usize> = ORDER.iter().map(|id| id.load(Ordering::SeqCst)).collect();
+        assert_eq!(order, [4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_array_new_zero_length() {
+        unsafe {
+            // new T[0] still yields a unique, deletable pointer
+            let ptr = fragile_rt_new_array(0, 4, 4);
+            assert!(!ptr.is_null());
+            fragile_rt_delete_array(ptr, 4, 4, None);
+        }
+    }
+
+    #[test]
+    fn test_aligned_alloc() {
+        unsafe {
+            let ptr = fragile_aligned_alloc(64, 256);
+            assert!(!ptr.is_null());
+            assert_eq!(ptr as usize % 64, 0);
+            // The whole block is usable
+            core::ptr::write_bytes(ptr as *mut u8, 0xab, 256);
+            assert_eq!(*(ptr as *mut u8).add(255), 0xab);
+            fragile_free(ptr);
+
+            // Alignment must be a power of two
+            assert!(fragile_aligned_alloc(48, 64).is_null());
+        }
+    }
+
+    #[test]
+    fn test_posix_memalign() {
+        unsafe {
+            let mut ptr: *mut c_void = core::ptr::null_mut();
+            assert_eq!(fragile_posix_memalign(&mut ptr, 64, 100), 0);
+            assert!(!ptr.is_null());
+            assert_eq!(ptr as usize % 64, 0);
+            fragile_free(ptr);
+
+            // Alignment must be a power of two multiple of sizeof(void*)
+            let mut bad: *mut c_void = core::ptr::null_mut();
+            assert_eq!(fragile_posix_memalign(&mut bad, 2, 16), libc::EINVAL);
+            assert_eq!(fragile_posix_memalign(&mut bad, 24, 16), libc::EINVAL);
+            assert!(bad.is_null());
+        }
+    }
+
     #[test]
     fn test_free_null() {
         unsafe {
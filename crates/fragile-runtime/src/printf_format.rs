@@ -0,0 +1,381 @@
+// printf-family formatting shared with transpiled crates.
+//
+// The transpiler embeds this file verbatim into the inline `fragile_runtime`
+// module of every generated crate, so it must stay self-contained: no `crate::`
+// paths, no inner attributes or `cfg`s, and only items from the std prelude.
+
+use core::ffi::{c_char, c_void};
+
+/// A single variadic argument to a printf-family call.
+///
+/// Defining C-variadic functions is unstable in Rust, so the transpiler packs
+/// the arguments after the format string into a slice of these at the call
+/// site. C's default argument promotions already happened in the C++ AST, so
+/// every value is an int, an unsigned int, a double or a pointer.
+#[derive(Debug, Clone, Copy)]
+pub enum PrintfArg {
+    Int(i64),
+    Uint(u64),
+    Double(f64),
+    Ptr(*const c_void),
+}
+
+macro_rules! printf_arg_from {
+    ($variant:ident as $repr:ty: $($ty:ty),*) => {
+        $(impl From<$ty> for PrintfArg {
+            fn from(value: $ty) -> Self {
+                PrintfArg::$variant(value as $repr)
+            }
+        })*
+    };
+}
+
+printf_arg_from!(Int as i64: i8, i16, i32, i64, isize, bool);
+printf_arg_from!(Uint as u64: u8, u16, u32, u64, usize);
+printf_arg_from!(Double as f64: f32, f64);
+
+impl<T> From<*const T> for PrintfArg {
+    fn from(value: *const T) -> Self {
+        PrintfArg::Ptr(value as *const c_void)
+    }
+}
+
+impl<T> From<*mut T> for PrintfArg {
+    fn from(value: *mut T) -> Self {
+        PrintfArg::Ptr(value as *const c_void)
+    }
+}
+
+impl PrintfArg {
+    fn as_i64(self) -> i64 {
+        match self {
+            PrintfArg::Int(v) => v,
+            PrintfArg::Uint(v) => v as i64,
+            PrintfArg::Double(v) => v as i64,
+            PrintfArg::Ptr(p) => p as usize as i64,
+        }
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            PrintfArg::Double(v) => v,
+            other => other.as_i64() as f64,
+        }
+    }
+}
+
+/// Expand a printf format string into bytes.
+///
+/// Supports the flags `-`, `+`, ` `, `0` and `#`, field width and precision
+/// (including `*`), the length modifiers `hh`, `h`, `l`, `ll`, `z`, `j` and `t`,
+/// and the conversions `d`, `i`, `u`, `o`, `x`, `X`, `c`, `s`, `f`, `F`, `e`, `E`,
+/// `g`, `G`, `a`, `A`, `p` and `%%`.
+/// Missing arguments read as zero; unknown conversions are copied through.
+pub(crate) unsafe fn format_printf(fmt: *const c_char, args: &[PrintfArg]) -> Vec<u8> {
+    let mut out = Vec::new();
+    if fmt.is_null() {
+        return out;
+    }
+    let fmt = std::ffi::CStr::from_ptr(fmt).to_bytes();
+    let mut args = args.iter().copied();
+    let mut next_arg = || args.next().unwrap_or(PrintfArg::Int(0));
+    let mut i = 0;
+    while i < fmt.len() {
+        if fmt[i] != b'%' {
+            out.push(fmt[i]);
+            i += 1;
+            continue;
+        }
+        let start = i;
+        i += 1;
+
+        // Flags
+        let (mut left, mut plus, mut space, mut zero, mut alt) =
+            (false, false, false, false, false);
+        while let Some(&c) = fmt.get(i) {
+            match c {
+                b'-' => left = true,
+                b'+' => plus = true,
+                b' ' => space = true,
+                b'0' => zero = true,
+                b'#' => alt = true,
+                _ => break,
+            }
+            i += 1;
+        }
+
+        // Width and precision; `*` takes the value from the argument list
+        let mut width = 0usize;
+        if fmt.get(i) == Some(&b'*') {
+            let w = next_arg().as_i64();
+            left |= w < 0;
+            width = w.unsigned_abs() as usize;
+            i += 1;
+        } else {
+            while let Some(d) = fmt.get(i).filter(|c| c.is_ascii_digit()) {
+                width = width * 10 + (d - b'0') as usize;
+                i += 1;
+            }
+        }
+        let mut precision = None;
+        if fmt.get(i) == Some(&b'.') {
+            i += 1;
+            if fmt.get(i) == Some(&b'*') {
+                precision = usize::try_from(next_arg().as_i64()).ok();
+                i += 1;
+            } else {
+                let mut p = 0usize;
+                while let Some(d) = fmt.get(i).filter(|c| c.is_ascii_digit()) {
+                    p = p * 10 + (d - b'0') as usize;
+                    i += 1;
+                }
+                precision = Some(p);
+            }
+        }
+
+        // Length modifiers: anything but none/hh/h reads a 64-bit value
+        let mut bits = 32;
+        while let Some(&c) = fmt.get(i) {
+            match c {
+                b'h' => bits = if bits == 16 { 8 } else { 16 },
+                b'l' | b'z' | b'j' | b't' | b'L' | b'q' => bits = 64,
+                _ => break,
+            }
+            i += 1;
+        }
+
+        let Some(&conv) = fmt.get(i) else {
+            out.extend_from_slice(&fmt[start..]);
+            break;
+        };
+        i += 1;
+
+        let hex_float_prefix: Vec<u8>;
+        let mut prefix: &[u8] = b"";
+        let body: Vec<u8> = match conv {
+            b'%' => {
+                out.push(b'%');
+                continue;
+            }
+            b'd' | b'i' => {
+                let v = next_arg().as_i64();
+                let v = match bits {
+                    8 => v as i8 as i64,
+                    16 => v as i16 as i64,
+                    32 => v as i32 as i64,
+                    _ => v,
+                };
+                if v < 0 {
+                    prefix = b"-";
+                } else if plus {
+                    prefix = b"+";
+                } else if space {
+                    prefix = b" ";
+                }
+                pad_digits(v.unsigned_abs().to_string().into_bytes(), precision)
+            }
+            b'u' | b'o' | b'x' | b'X' => {
+                let v = next_arg().as_i64() as u64;
+                let v = match bits {
+                    8 => v as u8 as u64,
+                    16 => v as u16 as u64,
+                    32 => v as u32 as u64,
+                    _ => v,
+                };
+                let digits = match conv {
+                    b'u' => v.to_string(),
+                    b'o' => format!("{:o}", v),
+                    b'x' => format!("{:x}", v),
+                    _ => format!("{:X}", v),
+                };
+                if alt && v != 0 {
+                    prefix = match conv {
+                        b'x' => b"0x",
+                        b'X' => b"0X",
+                        b'o' => b"0",
+                        _ => b"",
+                    };
+                }
+                pad_digits(digits.into_bytes(), precision)
+            }
+            b'f' | b'F' | b'e' | b'E' | b'g' | b'G' | b'a' | b'A' => {
+                let v = next_arg().as_f64();
+                if v.is_sign_negative() && !v.is_nan() {
+                    prefix = b"-";
+                } else if plus {
+                    prefix = b"+";
+                } else if space {
+                    prefix = b" ";
+                }
+                let digits = if v.is_infinite() {
+                    "inf".to_string()
+                } else if v.is_nan() {
+                    "nan".to_string()
+                } else {
+                    match conv.to_ascii_lowercase() {
+                        b'f' => format!("{:.*}", precision.unwrap_or(6), v.abs()),
+                        b'e' => format_exponent(v.abs(), precision.unwrap_or(6)),
+                        b'g' => format_general(v.abs(), precision.unwrap_or(6), alt),
+                        _ => {
+                            // The 0x goes with the sign so zero padding lands after it
+                            let x: &[u8] = if conv == b'A' { b"0X" } else { b"0x" };
+                            hex_float_prefix = [prefix, x].concat();
+                            prefix = &hex_float_prefix;
+                            format_hex_float(v.abs(), precision)
+                        }
+                    }
+                };
+                if conv.is_ascii_uppercase() {
+                    digits.to_uppercase().into_bytes()
+                } else {
+                    digits.into_bytes()
+                }
+            }
+            b'c' => vec![next_arg().as_i64() as u8],
+            b's' => match next_arg() {
+                PrintfArg::Ptr(p) if !p.is_null() => {
+                    let bytes = std::ffi::CStr::from_ptr(p as *const c_char).to_bytes();
+                    bytes[..precision.map_or(bytes.len(), |p| p.min(bytes.len()))].to_vec()
+                }
+                _ => b"(null)".to_vec(),
+            },
+            b'p' => {
+                let v = next_arg().as_i64() as usize;
+                if v == 0 {
+                    b"(nil)".to_vec()
+                } else {
+                    prefix = b"0x";
+                    format!("{:x}", v).into_bytes()
+                }
+            }
+            _ => {
+                // Unknown conversions still take their argument so later ones stay aligned
+                next_arg();
+                out.extend_from_slice(&fmt[start..i]);
+                continue;
+            }
+        };
+
+        // Zero padding goes between the sign/prefix and the digits, and is ignored
+        // when left-justifying or when an integer precision was given
+        let is_float = matches!(conv, b'f' | b'F' | b'e' | b'E' | b'g' | b'G' | b'a' | b'A');
+        let numeric = is_float || matches!(conv, b'd' | b'i' | b'u' | b'o' | b'x' | b'X');
+        let zero_pad = zero && !left && numeric && (precision.is_none() || is_float);
+        let fill = width.saturating_sub(prefix.len() + body.len());
+        if left {
+            out.extend_from_slice(prefix);
+            out.extend_from_slice(&body);
+            out.resize(out.len() + fill, b' ');
+        } else if zero_pad {
+            out.extend_from_slice(prefix);
+            out.resize(out.len() + fill, b'0');
+            out.extend_from_slice(&body);
+        } else {
+            out.resize(out.len() + fill, b' ');
+            out.extend_from_slice(prefix);
+            out.extend_from_slice(&body);
+        }
+    }
+    out
+}
+
+/// Format a non-negative finite value as `%e` does: `d.ddde+XX`, with at least two
+/// exponent digits.
+fn format_exponent(v: f64, precision: usize) -> String {
+    let formatted = format!("{:.*e}", precision, v);
+    let (mantissa, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+    let sign = if exponent < 0 { '-' } else { '+' };
+    format!("{}e{}{:02}", mantissa, sign, exponent.unsigned_abs())
+}
+
+/// Format a non-negative finite value as `%g` does: `%e` style for very small or large
+/// exponents, `%f` style otherwise, with trailing zeros removed unless `alt` (`#`) is set.
+fn format_general(v: f64, precision: usize, alt: bool) -> String {
+    let precision = precision.max(1);
+    // The exponent `%e` would print at this precision decides the style
+    let exponent: i32 = format!("{:.*e}", precision - 1, v)
+        .split_once('e')
+        .and_then(|(_, exp)| exp.parse().ok())
+        .unwrap_or(0);
+    let (digits, suffix) = if exponent < -4 || exponent >= precision as i32 {
+        let formatted = format_exponent(v, precision - 1);
+        let split = formatted.find('e').unwrap_or(formatted.len());
+        let (digits, suffix) = formatted.split_at(split);
+        (digits.to_string(), suffix.to_string())
+    } else {
+        let decimals = (precision as i32 - 1 - exponent) as usize;
+        (format!("{:.*}", decimals, v), String::new())
+    };
+    let digits = if !alt && digits.contains('.') {
+        digits.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &digits
+    };
+    format!("{}{}", digits, suffix)
+}
+
+/// Format a non-negative finite value as `%a` does, without the `0x` prefix:
+/// `h.hhhp+d`, printing the mantissa exactly when no precision is given.
+fn format_hex_float(v: f64, precision: Option<usize>) -> String {
+    let bits = v.to_bits();
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let mut mantissa = bits & ((1u64 << 52) - 1);
+    // Subnormals keep a leading 0 and the minimum exponent
+    let (mut leading, exponent) = match (biased_exponent, mantissa) {
+        (0, 0) => (0u64, 0),
+        (0, _) => (0, -1022),
+        _ => (1, biased_exponent - 1023),
+    };
+    let mut hex_digits = 13;
+    if let Some(p) = precision.filter(|&p| p < 13) {
+        // Round to nearest, ties to even, dropping the low hex digits
+        let shift = (13 - p) * 4;
+        let rem = mantissa & ((1u64 << shift) - 1);
+        let half = 1u64 << (shift - 1);
+        mantissa >>= shift;
+        let kept_is_odd = if p == 0 { leading & 1 } else { mantissa & 1 } == 1;
+        if rem > half || (rem == half && kept_is_odd) {
+            mantissa += 1;
+            if mantissa >> (p * 4) != 0 {
+                mantissa &= (1u64 << (p * 4)) - 1;
+                leading += 1;
+            }
+        }
+        hex_digits = p;
+    }
+    let mut fraction = if hex_digits == 0 {
+        String::new()
+    } else {
+        format!("{:0width$x}", mantissa, width = hex_digits)
+    };
+    match precision {
+        Some(p) => fraction.push_str(&"0".repeat(p.saturating_sub(13))),
+        None => fraction.truncate(fraction.trim_end_matches('0').len()),
+    }
+    let sign = if exponent < 0 { '-' } else { '+' };
+    if fraction.is_empty() {
+        format!("{}p{}{}", leading, sign, exponent.unsigned_abs())
+    } else {
+        format!(
+            "{}.{}p{}{}",
+            leading,
+            fraction,
+            sign,
+            exponent.unsigned_abs()
+        )
+    }
+}
+
+/// Apply an integer precision (minimum digit count); `%.0d` of zero prints nothing.
+fn pad_digits(digits: Vec<u8>, precision: Option<usize>) -> Vec<u8> {
+    match precision {
+        Some(0) if digits == b"0" => Vec::new(),
+        Some(p) if p > digits.len() => {
+            let mut padded = vec![b'0'; p - digits.len()];
+            padded.extend_from_slice(&digits);
+            padded
+        }
+        _ => digits,
+    }
+}
@@ -0,0 +1,250 @@
+//! POSIX thread-specific data (pthread_key) wrappers for transpiled C++ code.
+//!
+//! This module provides pthread_key-compatible functions that transpiled C++ code
+//! can call. libc++ uses these for thread-local storage (e.g. the per-thread
+//! exception globals and `thread_local` emulation).
+//!
+//! Keys are indices into a global slot table. Each thread keeps its values in a
+//! `thread_local!` table indexed by key id, and runs the registered destructors
+//! for its non-null values when it exits.
+//!
+//! IMPORTANT: All functions are prefixed with `fragile_` to avoid conflicts
+//! with system pthread functions. The transpiler must generate calls to these
+//! prefixed versions.
+
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::os::raw::c_int;
+use std::sync::Mutex;
+
+/// Destructor invoked with a thread's non-null value when that thread exits.
+pub type PthreadKeyDestructor = Option<unsafe extern "C" fn(*mut c_void)>;
+
+/// A slot in the global key table.
+struct KeySlot {
+    /// Whether the key is currently allocated
+    in_use: bool,
+    /// Bumped on every create/delete so values stored under a deleted key
+    /// are not visible through a later key that reuses the slot
+    generation: u64,
+    destructor: PthreadKeyDestructor,
+}
+
+static KEYS: Mutex<Vec<KeySlot>> = Mutex::new(Vec::new());
+
+/// Per-thread values, indexed by key id as `(generation, value)`.
+struct ThreadValues(Vec<(u64, *mut c_void)>);
+
+impl Drop for ThreadValues {
+    fn drop(&mut self) {
+        // Collect the destructors to run while holding the lock, then call
+        // them without it so they may use other keys
+        let pending: Vec<_> = {
+            let keys = KEYS.lock().unwrap_or_else(|e| e.into_inner());
+            self.0
+                .iter()
+                .enumerate()
+                .filter_map(|(key, &(generation, value))| {
+                    let slot = keys.get(key)?;
+                    if value.is_null() || !slot.in_use || slot.generation != generation {
+                        return None;
+                    }
+                    slot.destructor.map(|dtor| (dtor, value))
+                })
+                .collect()
+        };
+        self.0.clear();
+        for (dtor, value) in pending {
+            unsafe { dtor(value) };
+        }
+    }
+}
+
+thread_local! {
+    static VALUES: RefCell<ThreadValues> = const { RefCell::new(ThreadValues(Vec::new())) };
+}
+
+/// Current generation of `key`, or None if the key is not allocated.
+fn key_generation(key: u32) -> Option<u64> {
+    let keys = KEYS.lock().unwrap_or_else(|e| e.into_inner());
+    keys.get(key as usize)
+        .filter(|slot| slot.in_use)
+        .map(|slot| slot.generation)
+}
+
+/// Create a thread-specific data key.
+///
+/// # Arguments
+/// * `key` - Pointer to store the new key
+/// * `destructor` - Optional function called with each thread's non-null
+///   value when that thread exits
+///
+/// # Returns
+/// 0 on success, EINVAL if `key` is null, EAGAIN if no more keys are available
+#[no_mangle]
+pub extern "C" fn fragile_pthread_key_create(
+    key: *mut u32,
+    destructor: PthreadKeyDestructor,
+) -> c_int {
+    if key.is_null() {
+        return 22; // EINVAL
+    }
+
+    let mut keys = KEYS.lock().unwrap_or_else(|e| e.into_inner());
+    // Reuse a deleted slot if there is one
+    let index = match keys.iter().position(|slot| !slot.in_use) {
+        Some(index) => index,
+        None => {
+            if keys.len() > u32::MAX as usize {
+                return 11; // EAGAIN
+            }
+            keys.push(KeySlot {
+                in_use: false,
+                generation: 0,
+                destructor: None,
+            });
+            keys.len() - 1
+        }
+    };
+
+    let slot = &mut keys[index];
+    slot.in_use = true;
+    slot.generation += 1;
+    slot.destructor = destructor;
+
+    unsafe {
+        *key = index as u32;
+    }
+    0 // Success
+}
+
+/// Delete a thread-specific data key.
+///
+/// Destructors are not called for values still associated with the key.
+///
+/// # Returns
+/// 0 on success, EINVAL if the key is not allocated
+#[no_mangle]
+pub extern "C" fn fragile_pthread_key_delete(key: u32) -> c_int {
+    let mut keys = KEYS.lock().unwrap_or_else(|e| e.into_inner());
+    match keys.get_mut(key as usize) {
+        Some(slot) if slot.in_use => {
+            slot.in_use = false;
+            slot.generation += 1;
+            slot.destructor = None;
+            0 // Success
+        }
+        _ => 22, // EINVAL
+    }
+}
+
+/// Get the calling thread's value for a key.
+///
+/// # Returns
+/// The value set by this thread, or null if none was set or the key is invalid
+#[no_mangle]
+pub extern "C" fn fragile_pthread_getspecific(key: u32) -> *mut c_void {
+    let Some(generation) = key_generation(key) else {
+        return std::ptr::null_mut();
+    };
+    VALUES
+        .try_with(|values| match values.borrow().0.get(key as usize) {
+            Some(&(g, value)) if g == generation => value,
+            _ => std::ptr::null_mut(),
+        })
+        .unwrap_or(std::ptr::null_mut())
+}
+
+/// Set the calling thread's value for a key.
+///
+/// # Returns
+/// 0 on success, EINVAL if the key is not allocated, ENOMEM if the thread's
+/// values are already being destroyed
+#[no_mangle]
+pub extern "C" fn fragile_pthread_setspecific(key: u32, value: *const c_void) -> c_int {
+    let Some(generation) = key_generation(key) else {
+        return 22; // EINVAL
+    };
+    VALUES
+        .try_with(|values| {
+            let values = &mut values.borrow_mut().0;
+            let index = key as usize;
+            if values.len() <= index {
+                values.resize(index + 1, (0, std::ptr::null_mut()));
+            }
+            values[index] = (generation, value as *mut c_void);
+            0 // Success
+        })
+        .unwrap_or(12) // ENOMEM
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_key_create_delete() {
+        let mut key: u32 = 0;
+        assert_eq!(fragile_pthread_key_create(&mut key, None), 0);
+
+        assert!(fragile_pthread_getspecific(key).is_null());
+        assert_eq!(fragile_pthread_setspecific(key, 7 as *const c_void), 0);
+        assert_eq!(fragile_pthread_getspecific(key), 7 as *mut c_void);
+
+        assert_eq!(fragile_pthread_key_delete(key), 0);
+        assert_eq!(fragile_pthread_key_delete(key), 22);
+        // Values stored under a deleted key are gone even if the slot is reused
+        assert!(fragile_pthread_getspecific(key).is_null());
+    }
+
+    #[test]
+    fn test_values_are_per_thread() {
+        let mut key: u32 = 0;
+        assert_eq!(fragile_pthread_key_create(&mut key, None), 0);
+        assert_eq!(fragile_pthread_setspecific(key, 3 as *const c_void), 0);
+
+        let other = std::thread::spawn(move || {
+            // The value set by the main thread is not visible here
+            let before = fragile_pthread_getspecific(key) as usize;
+            assert_eq!(fragile_pthread_setspecific(key, 2 as *const c_void), 0);
+            (before, fragile_pthread_getspecific(key) as usize)
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(other, (0, 2));
+        assert_eq!(fragile_pthread_getspecific(key), 3 as *mut c_void);
+        assert_eq!(fragile_pthread_key_delete(key), 0);
+    }
+
+    static DESTROYED: AtomicUsize = AtomicUsize::new(0);
+
+    unsafe extern "C" fn count_destructor(value: *mut c_void) {
+        DESTROYED.fetch_add(value as usize, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn test_destructor_runs_on_thread_exit() {
+        let mut key: u32 = 0;
+        assert_eq!(
+            fragile_pthread_key_create(&mut key, Some(count_destructor)),
+            0
+        );
+
+        std::thread::spawn(move || {
+            assert_eq!(fragile_pthread_setspecific(key, 5 as *const c_void), 0);
+        })
+        .join()
+        .unwrap();
+        // Threads that never set a value don't call the destructor
+        std::thread::spawn(move || {
+            assert!(fragile_pthread_getspecific(key).is_null());
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(DESTROYED.load(Ordering::SeqCst), 5);
+        assert_eq!(fragile_pthread_key_delete(key), 0);
+    }
+}
@@ -0,0 +1,138 @@
+//! setjmp/longjmp support for transpiled C and C++ code.
+//!
+//! Rust has no way to return twice from a function, so this is a restricted
+//! implementation built on unwinding rather than saved register state:
+//!
+//! - `fragile_setjmp` arms a [`JmpBuf`] and returns the value of the jump that
+//!   landed on it (0 on the direct call).
+//! - `fragile_longjmp` records the value in the buffer and unwinds with a
+//!   sentinel payload naming the buffer.
+//! - [`fragile_setjmp_catch`] runs the region that called `setjmp`. When a jump
+//!   targeting its buffer unwinds out of the region, the region is re-run and
+//!   `setjmp` now returns the jumped value.
+//!
+//! Code executed in the region before the `setjmp` call therefore runs again
+//! after a jump, so the transpiler only wraps the statement containing the
+//! `setjmp` call (typically `if (setjmp(env) == 0) { ... } else { ... }`).
+//! Jumps to a buffer with no active region propagate like an uncaught panic.
+
+use core::ffi::c_int;
+
+/// Jump buffer (the `jmp_buf` element type, `struct __jmp_buf_tag`).
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JmpBuf {
+    /// Value passed to the `longjmp` that landed here (0 if none pending)
+    value: c_int,
+}
+
+impl JmpBuf {
+    /// Create an unarmed jump buffer.
+    pub const fn new() -> Self {
+        Self { value: 0 }
+    }
+}
+
+/// Unwind payload identifying the buffer a `longjmp` targets.
+#[cfg(feature = "std")]
+struct LongJmp(usize);
+
+/// Arm a jump buffer.
+///
+/// Returns 0 on the direct call, or the (non-zero) value passed to
+/// `fragile_longjmp` when the enclosing region is re-run after a jump.
+///
+/// # Safety
+/// `env` must point to a valid `JmpBuf`.
+#[no_mangle]
+pub unsafe extern "C" fn fragile_setjmp(env: *mut JmpBuf) -> c_int {
+    if env.is_null() {
+        return 0;
+    }
+    core::mem::take(&mut (*env).value)
+}
+
+/// Jump back to the `fragile_setjmp` that armed `env`, making it return `val`
+/// (or 1 if `val` is 0, as in C).
+///
+/// # Safety
+/// `env` must point to a valid `JmpBuf` armed by an active `fragile_setjmp_catch` region.
+#[cfg(feature = "std")]
+#[no_mangle]
+pub unsafe extern "C-unwind" fn fragile_longjmp(env: *mut JmpBuf, val: c_int) -> ! {
+    if !env.is_null() {
+        (*env).value = if val == 0 { 1 } else { val };
+    }
+    // "C-unwind" lets the jump cross the FFI boundary, and resume_unwind
+    // skips the panic hook so jumps don't print a panic message
+    std::panic::resume_unwind(Box::new(LongJmp(env as usize)))
+}
+
+/// Run `region` (the code containing the `setjmp(env)` call), re-running it each
+/// time a `longjmp` to `env` unwinds out of it. Jumps to other buffers and
+/// ordinary panics keep propagating.
+#[cfg(feature = "std")]
+pub fn fragile_setjmp_catch<R>(env: *mut JmpBuf, mut region: impl FnMut() -> R) -> R {
+    loop {
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(&mut region)) {
+            Ok(result) => return result,
+            Err(payload) => match payload.downcast::<LongJmp>() {
+                Ok(jump) if jump.0 == env as usize => continue,
+                Ok(jump) => std::panic::resume_unwind(jump),
+                Err(payload) => std::panic::resume_unwind(payload),
+            },
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    fn nested(env: *mut JmpBuf, depth: i32) -> i32 {
+        if depth == 0 {
+            unsafe { fragile_longjmp(env, 42) }
+        }
+        nested(env, depth - 1) + 1
+    }
+
+    #[test]
+    fn test_longjmp_out_of_nested_call() {
+        let mut env = JmpBuf::new();
+        let env_ptr = &mut env as *mut JmpBuf;
+        let mut runs = 0;
+        let result = fragile_setjmp_catch(env_ptr, || {
+            runs += 1;
+            match unsafe { fragile_setjmp(env_ptr) } {
+                0 => nested(env_ptr, 3),
+                val => val,
+            }
+        });
+        assert_eq!(result, 42);
+        assert_eq!(runs, 2);
+        // The landed value is consumed by setjmp
+        assert_eq!(unsafe { fragile_setjmp(env_ptr) }, 0);
+    }
+
+    #[test]
+    fn test_longjmp_value_and_outer_buffer() {
+        let mut outer = JmpBuf::new();
+        let mut inner = JmpBuf::new();
+        let (outer_ptr, inner_ptr) = (&mut outer as *mut JmpBuf, &mut inner as *mut JmpBuf);
+        let landed = fragile_setjmp_catch(outer_ptr, || {
+            let val = unsafe { fragile_setjmp(outer_ptr) };
+            if val != 0 {
+                return val;
+            }
+            // A jump to the outer buffer passes through the inner region
+            fragile_setjmp_catch(inner_ptr, || {
+                if unsafe { fragile_setjmp(inner_ptr) } == 0 {
+                    unsafe { fragile_longjmp(outer_ptr, 0) }
+                }
+                0
+            })
+        });
+        // longjmp(env, 0) makes setjmp return 1
+        assert_eq!(landed, 1);
+    }
+}
@@ -12,6 +12,7 @@
 //! - Buffer control: fflush, setvbuf
 //! - Error handling: ferror, feof, clearerr
 //! - Standard streams: stdin, stdout, stderr
+//! - Formatted output: printf, snprintf
 
 use core::ffi::{c_char, c_int, c_long, c_void};
 
@@ -22,6 +23,9 @@ use std::io::{Read, Seek, SeekFrom, Write};
 #[cfg(feature = "std")]
 use std::sync::Mutex;
 
+#[cfg(feature = "std")]
+use crate::printf_format::{format_printf, PrintfArg};
+
 /// EOF constant (matches C definition)
 pub const EOF: c_int = -1;
 
@@ -780,6 +784,60 @@ pub unsafe extern "C" fn fgets(s: *mut c_char, n: c_int, stream: *mut FILE) -> *
     s
 }
 
+// ============================================================================
+// Formatted output (printf family)
+// ============================================================================
+
+/// Write formatted output to stdout.
+///
+/// Returns the number of bytes written, or a negative value on error.
+///
+/// # Safety
+/// Caller must ensure `fmt` is a valid C string and that every `%s` argument
+/// is a valid C string.
+#[cfg(feature = "std")]
+pub unsafe fn fragile_printf(fmt: *const c_char, args: &[PrintfArg]) -> c_int {
+    let out = format_printf(fmt, args);
+    if out.is_empty() {
+        return 0;
+    }
+    if fwrite(
+        out.as_ptr() as *const c_void,
+        1,
+        out.len(),
+        __fragile_stdout(),
+    ) != out.len()
+    {
+        return -1;
+    }
+    out.len() as c_int
+}
+
+/// Write formatted output into `buf`, storing at most `n` bytes including the
+/// terminating null.
+///
+/// Returns the length the full output would have had, so a result `>= n`
+/// means the output was truncated.
+///
+/// # Safety
+/// Caller must ensure `buf` points to at least `n` writable bytes (it may be
+/// null when `n` is 0), plus the requirements of [`fragile_printf`].
+#[cfg(feature = "std")]
+pub unsafe fn fragile_snprintf(
+    buf: *mut c_char,
+    n: usize,
+    fmt: *const c_char,
+    args: &[PrintfArg],
+) -> c_int {
+    let out = format_printf(fmt, args);
+    if !buf.is_null() && n > 0 {
+        let len = out.len().min(n - 1);
+        core::ptr::copy_nonoverlapping(out.as_ptr(), buf as *mut u8, len);
+        *buf.add(len) = 0;
+    }
+    out.len() as c_int
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1026,4 +1084,95 @@ mod tests {
             std::fs::remove_file("/tmp/fragile_stdio_test3.txt").ok();
         }
     }
+
+    fn snprintf(fmt: &str, args: &[PrintfArg]) -> String {
+        let fmt = CString::new(fmt).unwrap();
+        let mut buf = [0 as c_char; 128];
+        let len = unsafe { fragile_snprintf(buf.as_mut_ptr(), buf.len(), fmt.as_ptr(), args) };
+        let out = unsafe { std::ffi::CStr::from_ptr(buf.as_ptr()) };
+        assert_eq!(len as usize, out.to_bytes().len());
+        out.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_snprintf_specifiers() {
+        let name = CString::new("fragile").unwrap();
+        assert_eq!(snprintf("%d %i", &[(-42).into(), 7.into()]), "-42 7");
+        assert_eq!(snprintf("%u", &[(-1i32 as u32).into()]), "4294967295");
+        assert_eq!(snprintf("%ld", &[(-1i64 << 40).into()]), "-1099511627776");
+        assert_eq!(snprintf("%s!", &[name.as_ptr().into()]), "fragile!");
+        assert_eq!(
+            snprintf("%c%c", &[(b'o' as i32).into(), (b'k' as i32).into()]),
+            "ok"
+        );
+        assert_eq!(
+            snprintf("%f %.2f", &[1.5.into(), (-2.345).into()]),
+            "1.500000 -2.35"
+        );
+        assert_eq!(
+            snprintf("%x %X %#x", &[255.into(), 255.into(), 16.into()]),
+            "ff FF 0x10"
+        );
+        assert_eq!(snprintf("100%%", &[]), "100%");
+        assert_eq!(
+            snprintf("[%5d|%-5d|%05d]", &[42.into(), 42.into(), (-42).into()]),
+            "[   42|42   |-0042]"
+        );
+        assert_eq!(snprintf("%.3s", &[name.as_ptr().into()]), "fra");
+        assert_eq!(
+            snprintf(
+                "%e %.2E %e",
+                &[1234.5.into(), (-0.000123).into(), 0.0.into()]
+            ),
+            "1.234500e+03 -1.23E-04 0.000000e+00"
+        );
+        assert_eq!(
+            snprintf(
+                "%g %g %g %.3g %#g",
+                &[
+                    100000.0.into(),
+                    1e6.into(),
+                    0.0001.into(),
+                    2.71928.into(),
+                    1.5.into()
+                ]
+            ),
+            "100000 1e+06 0.0001 2.72 1.50000"
+        );
+        assert_eq!(
+            snprintf(
+                "%a %A %.1a %a",
+                &[1.0.into(), (-0.5).into(), 1.96875.into(), 0.0.into()]
+            ),
+            "0x1p+0 -0X1P-1 0x2.0p+0 0x0p+0"
+        );
+        assert_eq!(
+            snprintf("%g %g %.0a", &[1e-5.into(), 123456789.0.into(), 1.5.into()]),
+            "1e-05 1.23457e+08 0x2p+0"
+        );
+        assert_eq!(snprintf("[%08.2e]", &[1.5.into()]), "[1.50e+00]");
+        assert_eq!(snprintf("[%010a]", &[1.0.into()]), "[0x00001p+0]");
+        // An unknown conversion is echoed and still consumes its argument
+        assert_eq!(snprintf("%y %d", &[1.into(), 2.into()]), "%y 2");
+
+        let p = 0x1234usize as *const c_void;
+        assert_eq!(snprintf("%p", &[p.into()]), "0x1234");
+    }
+
+    #[test]
+    fn test_snprintf_truncates() {
+        let fmt = CString::new("value=%d").unwrap();
+        let mut buf = [0x7f as c_char; 8];
+        unsafe {
+            // Reports the untruncated length and never writes past `n`
+            let len = fragile_snprintf(buf.as_mut_ptr(), 4, fmt.as_ptr(), &[12345.into()]);
+            assert_eq!(len, 11);
+            assert_eq!(std::ffi::CStr::from_ptr(buf.as_ptr()).to_bytes(), b"val");
+            assert_eq!(buf[4], 0x7f);
+
+            // A zero-sized buffer only measures
+            let len = fragile_snprintf(core::ptr::null_mut(), 0, fmt.as_ptr(), &[7.into()]);
+            assert_eq!(len, 7);
+        }
+    }
 }